@@ -0,0 +1,8 @@
+// Command line handling shared by the days' extra flags.
+
+// Prints the usage line to stderr and exits with status 2, the conventional code for
+// a bad invocation, instead of panicking.
+pub fn usage_error(usage: &str) -> ! {
+    eprintln!("usage: {}", usage);
+    std::process::exit(2)
+}
@@ -0,0 +1,201 @@
+// Lattice polygon helpers: shoelace area, Pick's theorem and point-in-polygon.
+//
+// A polygon is given by its vertices in walking order, without repeating the first
+// vertex at the end. Vertices can be the corners only (day18) or every tile along
+// the boundary (day10), both give the same results.
+
+use crate::number_theory::gcd;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn cross_product(&self, other: &Point) -> i64 {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Polygon {
+        Polygon { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (&Point, &Point)> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    /// Twice the signed area (shoelace formula), positive for counter-clockwise
+    /// vertices in a y-up frame. Kept doubled so it stays an integer.
+    pub fn signed_double_area(&self) -> i64 {
+        self.edges().map(|(p1, p2)| p1.cross_product(p2)).sum()
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_double_area().abs() as f64 / 2.0
+    }
+
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|(p1, p2)| {
+                let dx = (p2.x - p1.x) as f64;
+                let dy = (p2.y - p1.y) as f64;
+                (dx * dx + dy * dy).sqrt()
+            })
+            .sum()
+    }
+
+    /// Number of lattice points lying on the edges.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(p1, p2)| gcd((p2.x - p1.x).unsigned_abs(), (p2.y - p1.y).unsigned_abs()) as i64)
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside.
+    pub fn interior_points(&self) -> i64 {
+        pick_interior_points(
+            self.signed_double_area().abs() as i128,
+            self.boundary_points() as i128,
+        ) as i64
+    }
+
+    pub fn contains(&self, point: &Point) -> Location {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if is_on_segment(point, a, b) {
+                return Location::Boundary;
+            }
+            if (a.y > point.y) != (b.y > point.y) {
+                let dy = b.y - a.y;
+                let side = (a.x - point.x) * dy + (point.y - a.y) * (b.x - a.x);
+                if (side > 0) == (dy > 0) {
+                    inside = !inside;
+                }
+            }
+        }
+        if inside {
+            Location::Inside
+        } else {
+            Location::Outside
+        }
+    }
+}
+
+/// Pick's theorem, A = I + B/2 - 1, solved for I. Useful when the area and
+/// boundary are accumulated without keeping the vertices around.
+pub fn pick_interior_points(double_area: i128, boundary_points: i128) -> i128 {
+    (double_area - boundary_points + 2) / 2
+}
+
+fn is_on_segment(point: &Point, a: &Point, b: &Point) -> bool {
+    let cross = (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+    cross == 0
+        && point.x >= a.x.min(b.x)
+        && point.x <= a.x.max(b.x)
+        && point.y >= a.y.min(b.y)
+        && point.y <= a.y.max(b.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: i64) -> Polygon {
+        Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(size, 0),
+            Point::new(size, size),
+            Point::new(0, size),
+        ])
+    }
+
+    #[test]
+    fn test_shoelace() {
+        let polygon = Polygon::new(vec![
+            Point::new(1, 6),
+            Point::new(3, 1),
+            Point::new(7, 2),
+            Point::new(4, 4),
+            Point::new(8, 5),
+        ]);
+        assert_eq!(polygon.area(), 16.5);
+        assert_eq!(polygon.signed_double_area(), 33);
+    }
+
+    #[test]
+    fn test_perimeter() {
+        assert_eq!(square(3).perimeter(), 12.0);
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(3, 0), Point::new(0, 4)]);
+        assert_eq!(triangle.perimeter(), 12.0);
+    }
+
+    #[test]
+    fn test_pick() {
+        let polygon = square(3);
+        assert_eq!(polygon.boundary_points(), 12);
+        assert_eq!(polygon.interior_points(), 4);
+
+        let triangle = Polygon::new(vec![Point::new(0, 0), Point::new(4, 0), Point::new(0, 4)]);
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), 3);
+    }
+
+    #[test]
+    fn test_pick_with_every_boundary_tile() {
+        let polygon = Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(2, 0),
+            Point::new(2, 1),
+            Point::new(2, 2),
+            Point::new(1, 2),
+            Point::new(0, 2),
+            Point::new(0, 1),
+        ]);
+        assert_eq!(polygon.boundary_points(), 8);
+        assert_eq!(polygon.interior_points(), 1);
+    }
+
+    #[test]
+    fn test_contains() {
+        let polygon = Polygon::new(vec![
+            Point::new(0, 0),
+            Point::new(4, 0),
+            Point::new(4, 4),
+            Point::new(2, 2),
+            Point::new(0, 4),
+        ]);
+        assert_eq!(polygon.contains(&Point::new(1, 1)), Location::Inside);
+        assert_eq!(polygon.contains(&Point::new(3, 2)), Location::Inside);
+        assert_eq!(polygon.contains(&Point::new(2, 3)), Location::Outside);
+        assert_eq!(polygon.contains(&Point::new(5, 1)), Location::Outside);
+        assert_eq!(polygon.contains(&Point::new(2, 0)), Location::Boundary);
+        assert_eq!(polygon.contains(&Point::new(1, 3)), Location::Boundary);
+        assert_eq!(polygon.contains(&Point::new(4, 4)), Location::Boundary);
+    }
+}
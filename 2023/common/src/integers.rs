@@ -0,0 +1,174 @@
+// Pulls every integer out of a line of puzzle input, whatever separates them:
+// `Card 1: 41 48 | 83 86`, `1,0,1~1,2,1`, `seeds: 79 14`.
+//
+// Nothing is allocated; numbers are parsed straight from slices of the input. A `-`
+// right before a number makes it negative when the target type is signed, unless it
+// directly follows a digit, so `3-5` reads as 3 and 5 rather than 3 and -5.
+
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+pub trait Integer: Copy + FromStr {
+    const SIGNED: bool;
+}
+
+macro_rules! impl_integer {
+    ($signed:expr; $($t:ty),*) => {$(
+        impl Integer for $t {
+            const SIGNED: bool = $signed;
+        }
+    )*};
+}
+
+impl_integer!(false; u8, u16, u32, u64, u128, usize);
+impl_integer!(true; i8, i16, i32, i64, i128, isize);
+
+// The runs of ASCII digits in `text`, with a leading `-` kept when `signed`.
+#[derive(Debug, Clone)]
+pub struct DigitRuns<'a> {
+    text: &'a str,
+    position: usize,
+    signed: bool,
+}
+
+impl<'a> Iterator for DigitRuns<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let bytes = self.text.as_bytes();
+        let offset = bytes[self.position..].iter().position(u8::is_ascii_digit)?;
+        let digits = self.position + offset;
+        let end = bytes[digits..]
+            .iter()
+            .position(|byte| !byte.is_ascii_digit())
+            .map_or(bytes.len(), |length| digits + length);
+        let negative = self.signed
+            && digits > 0
+            && bytes[digits - 1] == b'-'
+            && (digits == 1 || !bytes[digits - 2].is_ascii_digit());
+        let start = if negative { digits - 1 } else { digits };
+        self.position = end;
+        Some(&self.text[start..end])
+    }
+}
+
+// Unsigned digit runs, e.g. to glue them into one number: `Time: 7 15 30` -> 71530.
+pub fn digit_runs(text: &str) -> DigitRuns<'_> {
+    DigitRuns {
+        text,
+        position: 0,
+        signed: false,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Integers<'a, T> {
+    runs: DigitRuns<'a>,
+    kind: PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = T;
+
+    // Panics on numbers that do not fit in `T`; puzzle input never asks for a lossy
+    // read.
+    fn next(&mut self) -> Option<T> {
+        let run = self.runs.next()?;
+        match run.parse::<T>() {
+            Ok(value) => Some(value),
+            Err(_) => panic!("{:?} does not fit in {}", run, std::any::type_name::<T>()),
+        }
+    }
+}
+
+pub fn integers<T: Integer>(text: &str) -> Integers<'_, T> {
+    Integers {
+        runs: DigitRuns {
+            text,
+            position: 0,
+            signed: T::SIGNED,
+        },
+        kind: PhantomData,
+    }
+}
+
+// The first `N` integers of `text`, `None` when there are fewer.
+pub fn array<T: Integer, const N: usize>(text: &str) -> Option<[T; N]> {
+    let mut values = integers::<T>(text);
+    let mut array = [None; N];
+    for slot in array.iter_mut() {
+        *slot = Some(values.next()?);
+    }
+    Some(array.map(Option::unwrap))
+}
+
+pub fn pair<T: Integer>(text: &str) -> Option<(T, T)> {
+    array(text).map(|[a, b]| (a, b))
+}
+
+pub fn triple<T: Integer>(text: &str) -> Option<(T, T, T)> {
+    array(text).map(|[a, b, c]| (a, b, c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn all<T: Integer>(text: &str) -> Vec<T> {
+        integers(text).collect()
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(all::<u32>("Card 1: 41 48 | 83  6"), vec![1, 41, 48, 83, 6]);
+        assert_eq!(all::<usize>("1,0,1~1,2,10"), vec![1, 0, 1, 1, 2, 10]);
+        assert_eq!(all::<i64>("x=-3, y=4, dx=-10"), vec![-3, 4, -10]);
+        assert_eq!(all::<u64>("x=-3, y=4"), vec![3, 4]);
+        assert_eq!(all::<i32>("3-5 -7--8"), vec![3, 5, -7, -8]);
+        assert_eq!(all::<i32>("-"), vec![]);
+        assert_eq!(all::<u8>("no numbers"), vec![]);
+        assert_eq!(
+            all::<u128>("340282366920938463463374607431768211455"),
+            vec![u128::MAX]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in u8")]
+    fn test_overflow() {
+        all::<u8>("256");
+    }
+
+    #[test]
+    fn test_digit_runs() {
+        let runs = digit_runs("Time:      7  15   30").collect::<Vec<&str>>();
+        assert_eq!(runs, vec!["7", "15", "30"]);
+        assert_eq!(digit_runs("Time: 7 15 30").collect::<String>(), "71530");
+        assert_eq!(digit_runs("a-1").next(), Some("1"));
+    }
+
+    #[test]
+    fn test_tuples() {
+        assert_eq!(pair::<u32>("10x20"), Some((10, 20)));
+        assert_eq!(triple::<usize>("1,0,1"), Some((1, 0, 1)));
+        assert_eq!(triple::<usize>("1,0"), None);
+        assert_eq!(
+            array::<i64, 4>("a -1 b 2 c -3 d 4 e 5"),
+            Some([-1, 2, -3, 4])
+        );
+        assert_eq!(array::<i64, 0>(""), Some([]));
+    }
+
+    proptest! {
+        #[test]
+        fn finds_every_number(values in prop::collection::vec(any::<i64>(), 0..20)) {
+            let text = values
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            prop_assert_eq!(all::<i64>(&text), values);
+        }
+    }
+}
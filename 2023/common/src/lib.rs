@@ -0,0 +1,5 @@
+pub mod cli;
+pub mod geometry;
+pub mod integers;
+pub mod number_theory;
+pub mod parse;
@@ -0,0 +1,273 @@
+// gcd/lcm, extended Euclid, modular arithmetic and the Chinese Remainder Theorem.
+//
+// Everything that can overflow is checked and returns `None` instead of wrapping.
+
+pub trait Unsigned:
+    Copy + PartialEq + std::ops::Rem<Output = Self> + std::ops::Div<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Unsigned for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u64, u128);
+
+pub fn gcd<T: Unsigned>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// `None` when the result does not fit in `T`. lcm(0, x) is 0.
+pub fn lcm<T: Unsigned>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+pub fn gcd_all<T: Unsigned, I: IntoIterator<Item = T>>(values: I) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+pub fn lcm_all<T: Unsigned, I: IntoIterator<Item = T>>(values: I) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| lcm(acc, value))
+}
+
+// Returns (g, x, y) with a * x + b * y = g = gcd(a, b), g >= 0.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// (a * b) % modulus without overflowing, even for moduli above 2^64.
+pub fn mul_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    let (mut a, mut b) = (a % modulus, b % modulus);
+    if a <= u64::MAX as u128 && b <= u64::MAX as u128 {
+        return a * b % modulus;
+    }
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, modulus);
+        }
+        a = add_mod(a, a, modulus);
+        b >>= 1;
+    }
+    result
+}
+
+fn add_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+fn sub_mod(a: u128, b: u128, modulus: u128) -> u128 {
+    if a >= b {
+        a - b
+    } else {
+        modulus - (b - a)
+    }
+}
+
+pub fn mod_pow(base: u128, exponent: u128, modulus: u128) -> u128 {
+    if modulus == 1 {
+        return 0;
+    }
+    let (mut base, mut exponent) = (base % modulus, exponent);
+    let mut result = 1;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+// The x in [0, modulus) with a * x = 1 (mod modulus), if a and modulus are coprime.
+// Runs extended Euclid with the coefficients kept modulo `modulus`, so it works for
+// the whole u128 range.
+pub fn mod_inverse(a: u128, modulus: u128) -> Option<u128> {
+    if modulus == 0 {
+        return None;
+    }
+    let (mut old_r, mut r) = (a % modulus, modulus);
+    let (mut old_s, mut s) = (1 % modulus, 0);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, sub_mod(old_s, mul_mod(quotient, s, modulus), modulus));
+    }
+    if old_r == 1 {
+        Some(old_s)
+    } else {
+        None
+    }
+}
+
+// Merges x = a (mod m) and x = b (mod n) into x = c (mod lcm(m, n)). The moduli do not
+// have to be coprime. `None` when the congruences contradict each other or the
+// combined modulus overflows.
+pub fn crt((a, m): (u128, u128), (b, n): (u128, u128)) -> Option<(u128, u128)> {
+    if m == 0 || n == 0 {
+        return None;
+    }
+    let (a, b) = (a % m, b % n);
+    let g = gcd(m, n);
+    let (difference, sign_flipped) = if b >= a {
+        (b - a, false)
+    } else {
+        (a - b, true)
+    };
+    if difference % g != 0 {
+        return None;
+    }
+    let combined = lcm(m, n)?;
+    let reduced = n / g;
+    // m * t = b - a (mod n)  =>  t = (b - a) / g * (m / g)^-1 (mod n / g)
+    let inverse = mod_inverse(m / g % reduced, reduced)?;
+    let mut t = mul_mod((difference / g) % reduced, inverse, reduced);
+    if sign_flipped {
+        t = sub_mod(0, t, reduced) % reduced;
+    }
+    Some(((a + m * t) % combined, combined))
+}
+
+pub fn crt_all<I: IntoIterator<Item = (u128, u128)>>(congruences: I) -> Option<(u128, u128)> {
+    congruences.into_iter().try_fold((0, 1), crt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(0u64, 7), 7);
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(0u64, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(
+            lcm(u64::MAX as u128, u64::MAX as u128 - 1),
+            Some((u64::MAX as u128) * (u64::MAX as u128 - 1))
+        );
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn test_mod_inverse_and_pow() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(2, 127, u128::MAX), 1 << 127);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt((2, 3), (3, 5)), Some((8, 15)));
+        assert_eq!(crt((3, 4), (1, 6)), Some((7, 12)));
+        assert_eq!(crt((0, 4), (1, 6)), None);
+        assert_eq!(crt_all([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt_all([(0, u128::MAX), (1, u128::MAX - 1)]), None);
+    }
+
+    proptest! {
+        #[test]
+        fn gcd_divides_both(a in 0u64.., b in 0u64..) {
+            let g = gcd(a, b);
+            if g == 0 {
+                prop_assert!(a == 0 && b == 0);
+            } else {
+                prop_assert_eq!(a % g, 0);
+                prop_assert_eq!(b % g, 0);
+                prop_assert_eq!(gcd(a / g, b / g), 1);
+            }
+        }
+
+        #[test]
+        fn lcm_times_gcd_is_product(a in 1u64.., b in 1u64..) {
+            let product = a as u128 * b as u128;
+            prop_assert_eq!(lcm(a as u128, b as u128).unwrap() * gcd(a, b) as u128, product);
+            match lcm(a, b) {
+                Some(l) => prop_assert_eq!(l as u128 * gcd(a, b) as u128, product),
+                None => prop_assert!(product / gcd(a, b) as u128 > u64::MAX as u128),
+            }
+        }
+
+        #[test]
+        fn extended_gcd_is_bezout(a in -1_000_000_000_000i128..1_000_000_000_000, b in -1_000_000_000_000i128..1_000_000_000_000) {
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(a * x + b * y, g);
+            prop_assert_eq!(g as u128, gcd(a.unsigned_abs(), b.unsigned_abs()));
+        }
+
+        #[test]
+        fn mod_inverse_inverts(a in 0u128.., modulus in 2u128..) {
+            match mod_inverse(a, modulus) {
+                Some(inverse) => prop_assert_eq!(mul_mod(a, inverse, modulus), 1),
+                None => prop_assert_ne!(gcd(a, modulus), 1),
+            }
+        }
+
+        #[test]
+        fn mod_pow_matches_repeated_multiplication(base in 0u128.., exponent in 0u32..200, modulus in 1u128..) {
+            let expected = (0..exponent).fold(1 % modulus, |acc, _| mul_mod(acc, base, modulus));
+            prop_assert_eq!(mod_pow(base, exponent as u128, modulus), expected);
+        }
+
+        #[test]
+        fn crt_satisfies_both(a in 0u128..1_000_000, m in 1u128..1_000_000, b in 0u128..1_000_000, n in 1u128..1_000_000) {
+            match crt((a, m), (b, n)) {
+                Some((x, modulus)) => {
+                    prop_assert_eq!(modulus, lcm(m, n).unwrap());
+                    prop_assert_eq!(x % m, a % m);
+                    prop_assert_eq!(x % n, b % n);
+                }
+                None => prop_assert_ne!((a % m).abs_diff(b % n) % gcd(m, n), 0),
+            }
+        }
+    }
+}
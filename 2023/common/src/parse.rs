@@ -0,0 +1,785 @@
+// A small parser-combinator layer for puzzle input.
+//
+// A parser runs on the whole input from a byte offset and returns the value with the
+// offset it stopped at, so every failure knows exactly where it happened. Grammars
+// are built from the primitives below (`tag`, `integer`, `word`, ...) with the
+// `Parser` methods, and `lines`, `sections` and `grid` cut the input into its usual
+// shapes. `parse` runs a grammar over a whole input and turns a failure into a
+// `ParseError` with line, column and the offending text.
+//
+// Alternatives (`or`) always backtrack. Repetitions (`repeated`, `separated_by`,
+// `optional`, `lines`, `sections`) stop quietly when the next item fails where it
+// starts, but report a failure from inside a partly matched item, which is what makes
+// the errors point at the actual mistake instead of at "end of input". A committed
+// failure, such as a number too large for its type, is reported even where it starts.
+
+use crate::integers::Integer;
+use std::fmt;
+
+// Byte offsets into the input, `end` exclusive.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Span {
+        Span { start, end }
+    }
+
+    pub fn at(offset: usize) -> Span {
+        Span::new(offset, offset)
+    }
+}
+
+// Why a parser stopped: what it would have accepted at `span`. A `committed` failure
+// is an error even when it starts where a repetition tried its next item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub span: Span,
+    pub expected: Vec<String>,
+    pub committed: bool,
+}
+
+impl Failure {
+    pub fn new(span: Span, expected: impl Into<String>) -> Failure {
+        Failure {
+            span,
+            expected: vec![expected.into()],
+            committed: false,
+        }
+    }
+
+    pub fn committed(span: Span, expected: impl Into<String>) -> Failure {
+        Failure {
+            committed: true,
+            ..Failure::new(span, expected)
+        }
+    }
+
+    // Whether the failure is an error rather than the end of a repetition whose next
+    // item was tried at `at`.
+    fn is_error_at(&self, at: usize) -> bool {
+        self.committed || self.span.start > at
+    }
+
+    // The failure that got furthest, or both sets of expectations on a tie.
+    fn furthest(self, other: Failure) -> Failure {
+        match self.span.start.cmp(&other.span.start) {
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Less => other,
+            std::cmp::Ordering::Equal => {
+                let mut expected = self.expected;
+                for item in other.expected {
+                    if !expected.contains(&item) {
+                        expected.push(item);
+                    }
+                }
+                Failure {
+                    span: Span::new(self.span.start, self.span.end.max(other.span.end)),
+                    expected,
+                    committed: self.committed || other.committed,
+                }
+            }
+        }
+    }
+}
+
+pub type PResult<T> = Result<(T, usize), Failure>;
+
+pub trait Parser<'a, T> {
+    fn run(&self, input: &'a str, at: usize) -> PResult<T>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (value, next) = self.run(input, at)?;
+            Ok((f(value), next))
+        }
+    }
+
+    // Replaces the value with a constant, e.g. `tag("red").to(Colour::Red)`.
+    fn to<U: Clone>(self, value: U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        self.map(move |_| value.clone())
+    }
+
+    // A check on the parsed value; the error describes what was expected instead and
+    // covers everything the parser consumed.
+    fn try_map<U>(self, f: impl Fn(T) -> Result<U, String>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (value, next) = self.run(input, at)?;
+            match f(value) {
+                Ok(value) => Ok((value, next)),
+                Err(expected) => Err(Failure::new(Span::new(at, next), expected)),
+            }
+        }
+    }
+
+    fn then<U>(self, other: impl Parser<'a, U>) -> impl Parser<'a, (T, U)>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (first, next) = self.run(input, at)?;
+            let (second, next) = other.run(input, next)?;
+            Ok(((first, second), next))
+        }
+    }
+
+    // Both in sequence, keeping only the second value.
+    fn ignore_then<U>(self, other: impl Parser<'a, U>) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        self.then(other).map(|(_, second)| second)
+    }
+
+    // Both in sequence, keeping only the first value.
+    fn then_ignore<U>(self, other: impl Parser<'a, U>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        self.then(other).map(|(first, _)| first)
+    }
+
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            self.run(input, at).or_else(|first| {
+                other
+                    .run(input, at)
+                    .map_err(|second| first.furthest(second))
+            })
+        }
+    }
+
+    fn optional(self) -> impl Parser<'a, Option<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| match self.run(input, at) {
+            Ok((value, next)) => Ok((Some(value), next)),
+            Err(failure) if failure.is_error_at(at) => Err(failure),
+            Err(_) => Ok((None, at)),
+        }
+    }
+
+    // Zero or more.
+    fn repeated(self) -> impl Parser<'a, Vec<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let mut values = vec![];
+            let mut position = at;
+            loop {
+                match self.run(input, position) {
+                    // An item that consumes nothing would repeat forever.
+                    Ok((value, next)) if next > position => {
+                        values.push(value);
+                        position = next;
+                    }
+                    Ok(_) => return Ok((values, position)),
+                    Err(failure) if failure.is_error_at(position) => return Err(failure),
+                    Err(_) => return Ok((values, position)),
+                }
+            }
+        }
+    }
+
+    // One or more.
+    fn repeated1(self) -> impl Parser<'a, Vec<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (first, next) = self.run(input, at)?;
+            let mut values = vec![first];
+            let rest = (|input: &'a str, at: usize| self.run(input, at)).repeated();
+            let (more, next) = rest.run(input, next)?;
+            values.extend(more);
+            Ok((values, next))
+        }
+    }
+
+    // One or more, with `separator` between them. A separator that is not followed by
+    // an item is left unconsumed.
+    fn separated_by<U>(self, separator: impl Parser<'a, U>) -> impl Parser<'a, Vec<T>>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (first, mut position) = self.run(input, at)?;
+            let mut values = vec![first];
+            loop {
+                let Ok((_, after_separator)) = separator.run(input, position) else {
+                    return Ok((values, position));
+                };
+                match self.run(input, after_separator) {
+                    Ok((value, next)) => {
+                        values.push(value);
+                        position = next;
+                    }
+                    Err(failure) if failure.is_error_at(after_separator) => return Err(failure),
+                    Err(_) => return Ok((values, position)),
+                }
+            }
+        }
+    }
+
+    // Skips spaces and tabs on both sides.
+    fn padded(self) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        spaces().ignore_then(self).then_ignore(spaces())
+    }
+
+    fn spanned(self) -> impl Parser<'a, (T, Span)>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (value, next) = self.run(input, at)?;
+            Ok(((value, Span::new(at, next)), next))
+        }
+    }
+
+    // The text the parser consumed instead of its value.
+    fn recognize(self) -> impl Parser<'a, &'a str>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            let (_, next) = self.run(input, at)?;
+            Ok((&input[at..next], next))
+        }
+    }
+
+    // Names what the parser expects, for failures where it starts.
+    fn labelled(self, name: &'static str) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str, at: usize| {
+            self.run(input, at).map_err(|failure| {
+                if failure.span.start == at && !failure.committed {
+                    Failure::new(failure.span, name)
+                } else {
+                    failure
+                }
+            })
+        }
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&'a str, usize) -> PResult<T>,
+{
+    fn run(&self, input: &'a str, at: usize) -> PResult<T> {
+        self(input, at)
+    }
+}
+
+// The character at `at` as a span, or an empty span at the end of the input.
+fn next_char(input: &str, at: usize) -> Span {
+    let length = input[at..].chars().next().map_or(0, char::len_utf8);
+    Span::new(at, at + length)
+}
+
+pub fn tag<'a>(text: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str, at: usize| {
+        if input[at..].starts_with(text) {
+            Ok((&input[at..at + text.len()], at + text.len()))
+        } else {
+            Err(Failure::new(next_char(input, at), format!("{:?}", text)))
+        }
+    }
+}
+
+// A single character accepted by `predicate`.
+pub fn satisfy<'a>(name: &'static str, predicate: impl Fn(char) -> bool) -> impl Parser<'a, char> {
+    move |input: &'a str, at: usize| match input[at..].chars().next() {
+        Some(c) if predicate(c) => Ok((c, at + c.len_utf8())),
+        _ => Err(Failure::new(next_char(input, at), name)),
+    }
+}
+
+// One character turned into a value by `cell`, e.g. a grid tile.
+pub fn cell<'a, T>(name: &'static str, value: impl Fn(char) -> Option<T>) -> impl Parser<'a, T> {
+    move |input: &'a str, at: usize| match input[at..].chars().next().and_then(&value) {
+        Some(value) => Ok((value, at + input[at..].chars().next().unwrap().len_utf8())),
+        None => Err(Failure::new(next_char(input, at), name)),
+    }
+}
+
+// The longest non-empty run of characters accepted by `predicate`.
+pub fn take_while1<'a>(
+    name: &'static str,
+    predicate: impl Fn(char) -> bool,
+) -> impl Parser<'a, &'a str> {
+    move |input: &'a str, at: usize| {
+        let length = input[at..]
+            .find(|c| !predicate(c))
+            .unwrap_or(input.len() - at);
+        if length == 0 {
+            Err(Failure::new(next_char(input, at), name))
+        } else {
+            Ok((&input[at..at + length], at + length))
+        }
+    }
+}
+
+// Letters, digits and underscores: names, labels and ids.
+pub fn word<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("word", |c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The rest of the line, which may be empty.
+pub fn rest_of_line<'a>() -> impl Parser<'a, &'a str> {
+    move |input: &'a str, at: usize| {
+        let end = input[at..]
+            .find(['\r', '\n'])
+            .map_or(input.len(), |i| at + i);
+        Ok((&input[at..end], end))
+    }
+}
+
+// A decimal integer, with a leading `-` for signed types.
+pub fn integer<'a, T: Integer>() -> impl Parser<'a, T> {
+    move |input: &'a str, at: usize| {
+        let bytes = input.as_bytes();
+        let sign = usize::from(T::SIGNED && bytes.get(at) == Some(&b'-'));
+        let digits = bytes[at + sign..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return Err(Failure::new(next_char(input, at), "number"));
+        }
+        let end = at + sign + digits;
+        match input[at..end].parse::<T>() {
+            Ok(value) => Ok((value, end)),
+            Err(_) => Err(Failure::committed(
+                Span::new(at, end),
+                format!("number that fits in {}", std::any::type_name::<T>()),
+            )),
+        }
+    }
+}
+
+// Zero or more spaces or tabs.
+pub fn spaces<'a>() -> impl Parser<'a, ()> {
+    move |input: &'a str, at: usize| {
+        let length = input[at..]
+            .find(|c| c != ' ' && c != '\t')
+            .unwrap_or(input.len() - at);
+        Ok(((), at + length))
+    }
+}
+
+// One or more spaces or tabs.
+pub fn spaces1<'a>() -> impl Parser<'a, ()> {
+    take_while1("space", |c| c == ' ' || c == '\t').to(())
+}
+
+pub fn newline<'a>() -> impl Parser<'a, ()> {
+    tag("\n").or(tag("\r\n")).to(()).labelled("newline")
+}
+
+pub fn end<'a>() -> impl Parser<'a, ()> {
+    move |input: &'a str, at: usize| {
+        if at == input.len() {
+            Ok(((), at))
+        } else {
+            Err(Failure::new(next_char(input, at), "end of input"))
+        }
+    }
+}
+
+// The end of the line content at `at`, without its `\r\n` or `\n`.
+fn line_end(input: &str, at: usize) -> (usize, Option<usize>) {
+    match input[at..].find('\n') {
+        Some(offset) => {
+            let newline = at + offset;
+            let content = if newline > at && input.as_bytes()[newline - 1] == b'\r' {
+                newline - 1
+            } else {
+                newline
+            };
+            (content, Some(newline + 1))
+        }
+        None => (input.len(), None),
+    }
+}
+
+// One `line` per line, up to a blank line or the end of the input. Every line must be
+// matched in full. Stops before the newline of the last line.
+pub fn lines<'a, T>(line: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str, at: usize| {
+        let mut values = vec![];
+        let mut position = at;
+        let mut last_end = at;
+        loop {
+            let (content_end, next_line) = line_end(input, position);
+            if content_end == position {
+                return Ok((values, last_end));
+            }
+            let (value, next) = line.run(input, position)?;
+            if next != content_end {
+                return Err(Failure::new(next_char(input, next), "end of line"));
+            }
+            values.push(value);
+            last_end = content_end;
+            match next_line {
+                Some(next_line) => position = next_line,
+                None => return Ok((values, last_end)),
+            }
+        }
+    }
+}
+
+// Blocks separated by blank lines; each block must be matched in full.
+pub fn sections<'a, T>(section: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str, at: usize| {
+        let (first, mut position) = section.run(input, at)?;
+        let mut values = vec![first];
+        loop {
+            let gap = input[position..]
+                .find(|c: char| !c.is_whitespace())
+                .map_or(input.len(), |offset| position + offset);
+            let newlines = input[position..gap].matches('\n').count();
+            if newlines < 2 || gap == input.len() {
+                return Ok((values, position));
+            }
+            // Sections start at the beginning of their line.
+            let start = input[..gap].rfind('\n').map_or(0, |offset| offset + 1);
+            let (value, next) = section.run(input, start)?;
+            let (content_end, _) = line_end(input, next);
+            if input[next..content_end].trim().is_empty() {
+                values.push(value);
+                position = next;
+            } else {
+                return Err(Failure::new(next_char(input, next), "end of line"));
+            }
+        }
+    }
+}
+
+// A rectangular block of characters, each turned into a value by `cell_value`.
+pub fn grid<'a, T>(
+    name: &'static str,
+    cell_value: impl Fn(char) -> Option<T>,
+) -> impl Parser<'a, Vec<Vec<T>>> {
+    let row = move |input: &'a str, at: usize| {
+        let (content_end, _) = line_end(input, at);
+        let mut cells = vec![];
+        for (offset, c) in input[at..content_end].char_indices() {
+            match cell_value(c) {
+                Some(value) => cells.push(value),
+                None => return Err(Failure::new(next_char(input, at + offset), name)),
+            }
+        }
+        Ok((cells, content_end))
+    };
+    let rows = lines(row.spanned());
+    move |input: &'a str, at: usize| {
+        let (rows, next) = rows.run(input, at)?;
+        let width = rows.first().map_or(0, |(row, _)| row.len());
+        if let Some((row, span)) = rows.iter().find(|(row, _)| row.len() != width) {
+            return Err(Failure::new(
+                *span,
+                format!("row of {} cells, not {}", width, row.len()),
+            ));
+        }
+        Ok((rows.into_iter().map(|(row, _)| row).collect(), next))
+    }
+}
+
+// A failure placed in the input: 1-based line and column (in characters), the text
+// of that line and what was found there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub span: Span,
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+    pub expected: Vec<String>,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(input: &str, failure: Failure) -> ParseError {
+        let Span { start, end } = failure.span;
+        let line_start = input[..start].rfind('\n').map_or(0, |offset| offset + 1);
+        let (content_end, _) = line_end(input, line_start);
+        // The offending text: the failure's span, or a single bad character with the
+        // rest of its word, and never past the end of the line.
+        let first = next_char(input, start).end;
+        let token_end = if end > first {
+            end
+        } else {
+            let word = input[start..content_end.max(start)]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(content_end.saturating_sub(start));
+            (start + word).max(first)
+        };
+        let token_end = token_end.min(content_end.max(start));
+        let found = if start == input.len() {
+            "end of input".to_string()
+        } else if token_end == start {
+            "end of line".to_string()
+        } else {
+            format!("{:?}", &input[start..token_end])
+        };
+        ParseError {
+            span: Span::new(start, token_end),
+            line: input[..start].matches('\n').count() + 1,
+            column: input[line_start..start].chars().count() + 1,
+            source_line: input[line_start..content_end].to_string(),
+            expected: failure.expected,
+            found,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expected = match self.expected.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+            None => "nothing".to_string(),
+        };
+        let offset = self
+            .source_line
+            .char_indices()
+            .nth(self.column - 1)
+            .map_or(self.source_line.len(), |(offset, _)| offset);
+        let marker = self
+            .source_line
+            .get(offset..offset + self.span.end - self.span.start)
+            .map_or(1, |text| text.chars().count().max(1));
+        writeln!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, expected, self.found
+        )?;
+        writeln!(f, "  {}", self.source_line)?;
+        write!(f, "  {}{}", " ".repeat(self.column - 1), "^".repeat(marker))
+    }
+}
+
+// Runs `parser` over the whole input; only whitespace may follow what it matched.
+pub fn parse<'a, T>(parser: impl Parser<'a, T>, input: &'a str) -> Result<T, ParseError> {
+    let (value, next) = parser
+        .run(input, 0)
+        .map_err(|failure| ParseError::new(input, failure))?;
+    let rest = input[next..]
+        .find(|c: char| !c.is_whitespace())
+        .map(|offset| next + offset);
+    match rest {
+        None => Ok(value),
+        Some(offset) => Err(ParseError::new(
+            input,
+            Failure::new(next_char(input, offset), "end of input"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    enum Colour {
+        Red,
+        Green,
+        Blue,
+    }
+
+    fn colour<'a>() -> impl Parser<'a, Colour> {
+        tag("red")
+            .to(Colour::Red)
+            .or(tag("green").to(Colour::Green))
+            .or(tag("blue").to(Colour::Blue))
+    }
+
+    // `Game 1: 3 blue, 4 red; 1 red, 2 green`
+    fn game<'a>() -> impl Parser<'a, (u32, Vec<Vec<(u32, Colour)>>)> {
+        let cubes = integer::<u32>().then_ignore(spaces1()).then(colour());
+        let draw = cubes.separated_by(tag(",").padded());
+        tag("Game ")
+            .ignore_then(integer())
+            .then_ignore(tag(":").padded())
+            .then(draw.separated_by(tag(";").padded()))
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(parse(tag("ab"), "ab"), Ok("ab"));
+        assert_eq!(parse(integer::<i32>(), "-12"), Ok(-12));
+        assert_eq!(parse(word(), "px_1"), Ok("px_1"));
+        assert_eq!(
+            parse(integer::<u8>().padded().repeated(), " 1  2 3 "),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(parse(integer::<u8>().repeated(), ""), Ok(vec![]));
+        assert_eq!(
+            parse(tag("x").optional().then(word()), "ab"),
+            Ok((None, "ab"))
+        );
+        assert_eq!(
+            parse(word().then_ignore(rest_of_line()), "ab  cd"),
+            Ok("ab")
+        );
+        assert_eq!(
+            parse(tag("a").then(tag("b")).recognize().spanned(), "ab"),
+            Ok(("ab", Span::new(0, 2)))
+        );
+        // A trailing separator is left for whatever follows.
+        let list = integer::<u8>()
+            .separated_by(tag(","))
+            .then_ignore(tag(",."));
+        assert_eq!(parse(list, "1,2,."), Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_game() {
+        let (id, draws) = parse(game(), "Game 12: 3 blue, 4 red; 1 red, 2 green").unwrap();
+        assert_eq!(id, 12);
+        assert_eq!(
+            draws,
+            vec![
+                vec![(3, Colour::Blue), (4, Colour::Red)],
+                vec![(1, Colour::Red), (2, Colour::Green)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = parse(game(), "Game 1: 3 blue, 4 rd").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 19));
+        assert_eq!(error.expected, vec!["\"red\"", "\"green\"", "\"blue\""]);
+        assert_eq!(error.found, "\"rd\"");
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 19: expected \"red\", \"green\" or \"blue\", found \"rd\"\n  \
+             Game 1: 3 blue, 4 rd\n                    ^^"
+        );
+        let error = parse(integer::<u8>(), "300").unwrap_err();
+        assert_eq!(error.expected, vec!["number that fits in u8"]);
+        assert_eq!(
+            (error.span, error.found.as_str()),
+            (Span::new(0, 3), "\"300\"")
+        );
+        // An overflow is reported even where a repetition tries its next item.
+        let numbers = integer::<u8>().separated_by(spaces1());
+        let error = parse(numbers, "1 300").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected[0].as_str()),
+            (3, "number that fits in u8")
+        );
+        let error = parse(integer::<u8>().padded().repeated(), "1 300").unwrap_err();
+        assert_eq!(error.span, Span::new(2, 5));
+        let error = parse(integer::<u8>().labelled("bid"), "300").unwrap_err();
+        assert_eq!(error.expected, vec!["number that fits in u8"]);
+        let error = parse(tag("a"), "a b").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected[0].as_str()),
+            (3, "end of input")
+        );
+        let error = parse(tag("ab"), "a").unwrap_err();
+        assert_eq!(error.found, "\"a\"");
+        let error = parse(tag("a").then(tag("b")), "a").unwrap_err();
+        assert_eq!(error.found, "end of input");
+        let labelled = integer::<u8>().labelled("bid");
+        assert_eq!(parse(labelled, "x").unwrap_err().expected, vec!["bid"]);
+        let checked = integer::<u32>().try_map(|n| {
+            if n % 2 == 0 {
+                Ok(n)
+            } else {
+                Err("even number".to_string())
+            }
+        });
+        let error = parse(checked, "17").unwrap_err();
+        assert_eq!(
+            (error.span, error.expected[0].as_str()),
+            (Span::new(0, 2), "even number")
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let input = "Game 1: 1 red\r\nGame 2: 2 blue\n\n";
+        let games = parse(lines(game()), input).unwrap();
+        assert_eq!(
+            games.iter().map(|game| game.0).collect::<Vec<u32>>(),
+            vec![1, 2]
+        );
+        let error = parse(lines(game()), "Game 1: 1 red\nGame 2: 2 blue 3 red").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+        assert_eq!(error.expected, vec!["end of line"]);
+        // A blank line ends the lines, and anything after it is left over.
+        let error = parse(lines(word()), "a\nb\n\nc").unwrap_err();
+        assert_eq!(
+            (error.line, error.expected[0].as_str()),
+            (4, "end of input")
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let section = word()
+            .then_ignore(tag(":"))
+            .then_ignore(newline())
+            .then(lines(integer::<u8>()));
+        let input = "a:\n1\n2\n\n\nb:\n3\n";
+        let parsed = parse(sections(section), input).unwrap();
+        assert_eq!(parsed, vec![("a", vec![1, 2]), ("b", vec![3])]);
+        let numbers = parse(sections(lines(integer::<u8>())), "1\n\n2\r\n\r\n3").unwrap();
+        assert_eq!(numbers, vec![vec![1], vec![2], vec![3]]);
+        let section = word()
+            .then_ignore(tag(":"))
+            .then_ignore(newline())
+            .then(lines(integer::<u8>()));
+        let error = parse(sections(section), "a:\n1\n\nb:\nx").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.expected[0].as_str()),
+            (5, 1, "number")
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let tile = |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        };
+        assert_eq!(
+            parse(grid("tile", tile), "#.\n.#\n"),
+            Ok(vec![vec![true, false], vec![false, true]])
+        );
+        let error = parse(grid("tile", tile), "#.\n.x").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.expected[0].as_str()),
+            (2, 2, "tile")
+        );
+        let error = parse(grid("tile", tile), "#.\n.#.").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, vec!["row of 2 cells, not 3"]);
+    }
+}
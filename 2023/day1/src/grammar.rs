@@ -0,0 +1,41 @@
+use common::parse::{parse, take_while1, ParseError, Parser};
+
+// A calibration value: a mix of letters and digits.
+fn calibration_line<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("letter or digit", |c| c.is_ascii_alphanumeric())
+}
+
+// The calibration values one line at a time, borrowed from `input`; blank lines are
+// skipped and errors keep the line they were found on.
+pub fn calibration_lines(input: &str) -> impl Iterator<Item = Result<&str, ParseError>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse(calibration_line(), line).map_err(|mut error| {
+                error.line = index + 1;
+                error
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_lines() {
+        assert_eq!(
+            calibration_lines("1abc2\npqr3stu8vwx\n").collect::<Result<Vec<&str>, ParseError>>(),
+            Ok(vec!["1abc2", "pqr3stu8vwx"])
+        );
+        let error = calibration_lines("1abc2\npqr 3")
+            .collect::<Result<Vec<&str>, ParseError>>()
+            .unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.found),
+            (2, 5, "\"3\"".to_string())
+        );
+    }
+}
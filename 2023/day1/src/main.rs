@@ -1,13 +1,35 @@
+mod grammar;
 mod part1;
 mod part2;
+mod scanner;
+mod vocabulary;
+
+use common::cli::usage_error;
 
 fn main() {
     run_part1();
     run_part2();
+
+    // `cargo run -- --vocabulary src/vocabularies/german.txt` reruns part 2 with other
+    // digit words
+    let args = std::env::args().collect::<Vec<String>>();
+    if let Some(position) = args.iter().position(|arg| arg == "--vocabulary") {
+        let vocabulary_path = args
+            .get(position + 1)
+            .unwrap_or_else(|| usage_error("cargo run -- --vocabulary <words.txt>"));
+        match vocabulary::Vocabulary::from_file(vocabulary_path) {
+            Ok(vocabulary) => {
+                let input = part2::read_input("src/inputs/input.txt");
+                let total = part2::total_with(&vocabulary.scanner(), &input);
+                println!("Total part2 with {}: {}", vocabulary_path, total);
+            }
+            Err(error) => println!("{}", error),
+        }
+    }
 }
 
 fn run_part1() {
-    let input_path = r"src\inputs\input.txt";
+    let input_path = "src/inputs/input.txt";
     let lines = part1::read_input(input_path);
     let mut total = 0;
     for line in lines {
@@ -17,11 +39,8 @@ fn run_part1() {
 }
 
 fn run_part2() {
-    let input_path = r"src\inputs\input.txt";
-    let lines = part2::read_input(input_path);
-    let mut total = 0;
-    for line in lines {
-        total += part2::get_line_code(&line);
-    }
+    let input_path = "src/inputs/input.txt";
+    let input = part2::read_input(input_path);
+    let total = part2::total(&input);
     println!("Total part2: {}", total);
 }
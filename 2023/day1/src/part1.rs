@@ -1,17 +1,16 @@
-use regex::Regex;
+use crate::grammar::calibration_lines;
 use std::fs::read_to_string;
 
 pub fn read_input(input_path: &str) -> Vec<String> {
-    read_to_string(input_path)
-        .unwrap()
-        .lines()
-        .map(String::from)
-        .collect()
+    let text = read_to_string(input_path).unwrap();
+    calibration_lines(&text)
+        .map(|line| line.map(String::from))
+        .collect::<Result<Vec<String>, _>>()
+        .unwrap_or_else(|error| panic!("{}", error))
 }
 
 fn strip_non_numbers(text: &str) -> String {
-    let re = Regex::new(r"[^0-9]").unwrap();
-    return re.replace_all(&text, "").to_string();
+    text.chars().filter(char::is_ascii_digit).collect()
 }
 
 pub fn get_line_code(text: &str) -> u32 {
@@ -1,81 +1,37 @@
-use regex::Regex;
-use std::collections::HashMap;
+use crate::grammar::calibration_lines;
+use crate::scanner::DigitScanner;
+use crate::vocabulary::Vocabulary;
 use std::fs::read_to_string;
+use std::sync::OnceLock;
 
-pub fn read_input(input_path: &str) -> Vec<String> {
-    read_to_string(input_path)
-        .unwrap()
-        .lines()
-        .map(String::from)
-        .collect()
+pub fn read_input(input_path: &str) -> String {
+    read_to_string(input_path).unwrap()
 }
 
-struct SearchResult {
-    searched_word: String,
-    start_index: usize,
-}
-
-fn find_word_occurrences(text: &str, word: &str) -> Vec<SearchResult> {
-    let mut results = Vec::new();
-    let regex_pattern = format!(r"{}", regex::escape(word));
-    let re = Regex::new(&regex_pattern).unwrap();
-
-    for mat in re.find_iter(text) {
-        results.push(SearchResult {
-            searched_word: word.to_string(),
-            start_index: mat.start(),
-        });
-    }
-
-    results
+// Built on first use and shared by every line.
+pub fn scanner() -> &'static DigitScanner {
+    static SCANNER: OnceLock<DigitScanner> = OnceLock::new();
+    SCANNER.get_or_init(|| Vocabulary::english().scanner())
 }
 
 pub fn get_line_code(text: &str) -> u32 {
-    let trad_dict: HashMap<&str, u32> = HashMap::from([
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-        ("1", 1),
-        ("2", 2),
-        ("3", 3),
-        ("4", 4),
-        ("5", 5),
-        ("6", 6),
-        ("7", 7),
-        ("8", 8),
-        ("9", 9),
-    ]);
-
-    let mut ocurrs: Vec<SearchResult> = Vec::new();
-
-    for (word, _) in &trad_dict {
-        ocurrs.append(&mut find_word_occurrences(text, word));
-    }
-
-    if ocurrs.len() == 0 {
-        return 0;
-    }
-
-    ocurrs.sort_by_key(|ocurr| ocurr.start_index);
+    scanner().line_code(text)
+}
 
-    let left = ocurrs
-        .first()
-        .map(|m| trad_dict.get(m.searched_word.as_str()))
-        .unwrap()
-        .unwrap();
-    let right = match ocurrs.last() {
-        Some(c) => trad_dict.get(c.searched_word.as_str()).unwrap(),
-        None => left,
-    };
+// One pass over the whole input, borrowing each line in place.
+pub fn total(input: &str) -> u32 {
+    calibration_lines(input)
+        .map(|line| line.map(get_line_code))
+        .sum::<Result<u32, _>>()
+        .unwrap_or_else(|error| panic!("{}", error))
+}
 
-    let res = 10 * left + right;
-    return res;
+// The same with a scanner built from another vocabulary.
+pub fn total_with(scanner: &DigitScanner, input: &str) -> u32 {
+    calibration_lines(input)
+        .map(|line| line.map(|line| scanner.line_code(line)))
+        .sum::<Result<u32, _>>()
+        .unwrap_or_else(|error| panic!("{}", error))
 }
 
 #[cfg(test)]
@@ -101,5 +57,20 @@ mod tests {
         assert_eq!(get_line_code("4nineeightseven2"), 42);
         assert_eq!(get_line_code("zoneight234"), 14);
         assert_eq!(get_line_code("7pqrstsixteen"), 76);
+        assert_eq!(get_line_code("twone"), 21);
+        assert_eq!(get_line_code("eightwo"), 82);
+        assert_eq!(get_line_code("abc"), 0);
+    }
+
+    #[test]
+    fn total_test() {
+        let input = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+        assert_eq!(total(input), 281);
     }
 }
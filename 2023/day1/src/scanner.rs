@@ -0,0 +1,132 @@
+// Aho-Corasick automaton over a vocabulary of digit words. The goto and failure
+// links are folded into one dense transition table, so scanning is a single table
+// lookup per byte and every match, overlapping ones included, is reported at the
+// byte where it ends.
+
+const ALPHABET: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Match {
+    start: usize,
+    len: usize,
+    value: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct DigitScanner {
+    transitions: Vec<[u32; ALPHABET]>,
+    // Patterns (length, value) ending at each state, following the failure links.
+    outputs: Vec<Vec<(usize, u32)>>,
+}
+
+impl DigitScanner {
+    pub fn new<'a, I: IntoIterator<Item = (&'a str, u32)>>(patterns: I) -> DigitScanner {
+        let mut transitions = vec![[0; ALPHABET]];
+        let mut outputs = vec![vec![]];
+        let mut has_edge = vec![[false; ALPHABET]];
+        for (pattern, value) in patterns {
+            assert!(!pattern.is_empty(), "Empty pattern for value {}", value);
+            let mut state = 0;
+            for byte in pattern.bytes() {
+                if !has_edge[state][byte as usize] {
+                    has_edge[state][byte as usize] = true;
+                    transitions[state][byte as usize] = transitions.len() as u32;
+                    transitions.push([0; ALPHABET]);
+                    outputs.push(vec![]);
+                    has_edge.push([false; ALPHABET]);
+                }
+                state = transitions[state][byte as usize] as usize;
+            }
+            outputs[state].push((pattern.len(), value));
+        }
+
+        // Breadth first, so a state's failure target is complete before its children
+        // copy from it. Missing edges fall through to the failure state's edges.
+        let mut failure = vec![0; transitions.len()];
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..ALPHABET {
+                let fallback = transitions[failure[state]][byte];
+                if has_edge[state][byte] {
+                    let child = transitions[state][byte] as usize;
+                    failure[child] = if state == 0 { 0 } else { fallback as usize };
+                    let inherited = outputs[failure[child]].clone();
+                    outputs[child].extend(inherited);
+                    queue.push_back(child);
+                } else if state != 0 {
+                    transitions[state][byte] = fallback;
+                }
+            }
+        }
+
+        DigitScanner {
+            transitions,
+            outputs,
+        }
+    }
+
+    // The values of the leftmost and rightmost matches in `line`, by start position.
+    // Matches starting at the same byte are resolved in favour of the longer one.
+    pub fn first_and_last(&self, line: &str) -> Option<(u32, u32)> {
+        let mut first: Option<Match> = None;
+        let mut last: Option<Match> = None;
+        let mut state = 0;
+        for (index, byte) in line.bytes().enumerate() {
+            state = self.transitions[state][byte as usize] as usize;
+            for (len, value) in &self.outputs[state] {
+                let found = Match {
+                    start: index + 1 - len,
+                    len: *len,
+                    value: *value,
+                };
+                if first.is_none_or(|m| (found.start, m.len) < (m.start, found.len)) {
+                    first = Some(found);
+                }
+                if last.is_none_or(|m| (found.start, found.len) > (m.start, m.len)) {
+                    last = Some(found);
+                }
+            }
+        }
+        Some((first?.value, last?.value))
+    }
+
+    // The first digit of the first match and the last digit of the last one, as a
+    // two digit number, or 0 for a line without any match. Values above 9 stand for
+    // all of their digits.
+    pub fn line_code(&self, line: &str) -> u32 {
+        self.first_and_last(line)
+            .map_or(0, |(first, last)| 10 * leading_digit(first) + last % 10)
+    }
+}
+
+fn leading_digit(mut value: u32) -> u32 {
+    while value >= 10 {
+        value /= 10;
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_words() {
+        let scanner = DigitScanner::new([("one", 1), ("two", 2), ("eight", 8), ("1", 1)]);
+        assert_eq!(scanner.first_and_last("twone"), Some((2, 1)));
+        assert_eq!(scanner.first_and_last("eightwo"), Some((8, 2)));
+        assert_eq!(scanner.first_and_last("xx1xx"), Some((1, 1)));
+        assert_eq!(scanner.first_and_last("nothing"), None);
+        assert_eq!(scanner.line_code("nothing"), 0);
+    }
+
+    #[test]
+    fn test_failure_links_and_shared_starts() {
+        // "she" has to report "he" through its failure link, and "ab" / "abcd" start
+        // at the same byte.
+        let scanner = DigitScanner::new([("he", 1), ("she", 2), ("ab", 3), ("abcd", 4), ("bc", 5)]);
+        assert_eq!(scanner.first_and_last("ushe"), Some((2, 1)));
+        assert_eq!(scanner.first_and_last("abcd"), Some((4, 5)));
+        assert_eq!(scanner.first_and_last("abcx"), Some((3, 5)));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::scanner::DigitScanner;
+use std::fmt;
+
+// The words a calibration line can spell its digits with, each mapped to the number
+// it stands for. Numbers above 9 (`ten`, `twelve`) count as their written digits, so
+// `twelve` is a 1 when it comes first on a line and a 2 when it comes last.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VocabularyError {
+    MissingValue(usize),
+    InvalidValue(usize, String),
+    EmptyWord(usize),
+    Unreadable(String),
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::MissingValue(line) => {
+                write!(f, "line {}: expected `word = value`", line)
+            }
+            VocabularyError::InvalidValue(line, value) => {
+                write!(f, "line {}: invalid value {:?}", line, value)
+            }
+            VocabularyError::EmptyWord(line) => write!(f, "line {}: empty word", line),
+            VocabularyError::Unreadable(error) => write!(f, "cannot read vocabulary: {}", error),
+        }
+    }
+}
+
+impl Vocabulary {
+    pub fn new() -> Vocabulary {
+        Vocabulary::default()
+    }
+
+    pub fn word(mut self, word: &str, value: u32) -> Vocabulary {
+        self.words.push((word.to_string(), value));
+        self
+    }
+
+    // The plain digits 1 to 9.
+    pub fn digits(self) -> Vocabulary {
+        (1..=9).fold(self, |vocabulary, digit| {
+            vocabulary.word(&digit.to_string(), digit)
+        })
+    }
+
+    // The puzzle's own vocabulary: digits and `one` to `nine`.
+    pub fn english() -> Vocabulary {
+        [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ]
+        .iter()
+        .zip(1..)
+        .fold(Vocabulary::new(), |vocabulary, (word, value)| {
+            vocabulary.word(word, value)
+        })
+        .digits()
+    }
+
+    // One `word = value` pair per line; blank lines and `#` comments are skipped.
+    // Digits are not implied, a file that wants them lists them or is loaded with
+    // `.digits()` afterwards.
+    pub fn parse(text: &str) -> Result<Vocabulary, VocabularyError> {
+        let mut vocabulary = Vocabulary::new();
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (word, value) = line
+                .split_once('=')
+                .ok_or(VocabularyError::MissingValue(line_number))?;
+            let (word, value) = (word.trim(), value.trim());
+            if word.is_empty() {
+                return Err(VocabularyError::EmptyWord(line_number));
+            }
+            let value = value
+                .parse::<u32>()
+                .map_err(|_| VocabularyError::InvalidValue(line_number, value.to_string()))?;
+            vocabulary = vocabulary.word(word, value);
+        }
+        Ok(vocabulary)
+    }
+
+    pub fn from_file(path: &str) -> Result<Vocabulary, VocabularyError> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| VocabularyError::Unreadable(format!("{}: {}", path, error)))?;
+        Vocabulary::parse(&text)
+    }
+
+    pub fn scanner(&self) -> DigitScanner {
+        DigitScanner::new(
+            self.words
+                .iter()
+                .map(|(word, value)| (word.as_str(), *value)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse(
+            "# German
+eins = 1
+zwei=2
+
+drei = 3 # trailing comment",
+        )
+        .unwrap();
+        assert_eq!(
+            vocabulary,
+            Vocabulary::new()
+                .word("eins", 1)
+                .word("zwei", 2)
+                .word("drei", 3)
+        );
+        assert_eq!(
+            Vocabulary::parse("eins"),
+            Err(VocabularyError::MissingValue(1))
+        );
+        assert_eq!(
+            Vocabulary::parse("\neins = x"),
+            Err(VocabularyError::InvalidValue(2, "x".to_string()))
+        );
+        assert_eq!(
+            Vocabulary::parse(" = 1"),
+            Err(VocabularyError::EmptyWord(1))
+        );
+    }
+
+    #[test]
+    fn test_other_vocabularies() {
+        let german = Vocabulary::new()
+            .word("eins", 1)
+            .word("zwei", 2)
+            .word("acht", 8)
+            .digits()
+            .scanner();
+        assert_eq!(german.line_code("xachtzweins"), 81);
+        assert_eq!(german.line_code("one7two"), 77);
+
+        let ordinals = Vocabulary::new()
+            .word("first", 1)
+            .word("third", 3)
+            .scanner();
+        assert_eq!(ordinals.line_code("thirdandfirst"), 31);
+    }
+
+    #[test]
+    fn test_multi_digit_words() {
+        let scanner = Vocabulary::english()
+            .word("ten", 10)
+            .word("twelve", 12)
+            .scanner();
+        assert_eq!(scanner.line_code("twelve3"), 13);
+        assert_eq!(scanner.line_code("4xtwelve"), 42);
+        assert_eq!(scanner.line_code("tenine"), 19);
+        assert_eq!(scanner.line_code("ninetwelve"), 92);
+    }
+}
@@ -0,0 +1,35 @@
+use common::parse::{grid, Parser};
+
+// The rows of pipe characters and where the single `S` sits.
+pub(crate) fn maze<'a>() -> impl Parser<'a, (Vec<Vec<char>>, (usize, usize))> {
+    grid("pipe, ground or S", |c| "|-LJ7F.S".contains(c).then_some(c)).try_map(|rows| {
+        let starts = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, c)| (x, y, *c)))
+            .filter(|(_, _, c)| *c == 'S')
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<(usize, usize)>>();
+        match starts[..] {
+            [start] => Ok((rows, start)),
+            _ => Err(format!("a single S tile, not {}", starts.len())),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_maze() {
+        let (rows, start) = parse(maze(), ".S\n.|\n").unwrap();
+        assert_eq!(rows, vec![vec!['.', 'S'], vec!['.', '|']]);
+        assert_eq!(start, (1, 0));
+        let error = parse(maze(), ".S\n.x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        let error = parse(maze(), "..\n..").unwrap_err();
+        assert_eq!(error.expected, vec!["a single S tile, not 0".to_string()]);
+    }
+}
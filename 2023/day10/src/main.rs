@@ -1,7 +1,24 @@
+mod grammar;
+mod maze;
 mod part1;
 mod part2;
+mod render;
 
 fn main() {
     part1::main();
     part2::main();
+
+    // `cargo run -- --render` draws the real maze, `--render-plain` without colours
+    let args = std::env::args().collect::<Vec<String>>();
+    let style = if args.iter().any(|arg| arg == "--render") {
+        Some(render::Style::Ansi)
+    } else if args.iter().any(|arg| arg == "--render-plain") {
+        Some(render::Style::Plain)
+    } else {
+        None
+    };
+    if let Some(style) = style {
+        let input = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+        println!("{}", render::render(&maze::Maze::parse(&input), style));
+    }
 }
@@ -0,0 +1,271 @@
+use crate::grammar;
+use common::parse::parse;
+
+// | is a vertical pipe connecting north and south.
+// - is a horizontal pipe connecting east and west.
+// L is a 90-degree bend connecting north and east.
+// J is a 90-degree bend connecting north and west.
+// 7 is a 90-degree bend connecting south and west.
+// F is a 90-degree bend connecting south and east.
+// S is the starting position, sitting on a pipe whose shape is not shown.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Tile {
+    Vertical,
+    Horizontal,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+}
+
+impl Tile {
+    const PIPES: [Tile; 6] = [
+        Tile::Vertical,
+        Tile::Horizontal,
+        Tile::NorthEast,
+        Tile::NorthWest,
+        Tile::SouthWest,
+        Tile::SouthEast,
+    ];
+
+    fn from_char(c: char) -> Tile {
+        match c {
+            '|' => Tile::Vertical,
+            '-' => Tile::Horizontal,
+            'L' => Tile::NorthEast,
+            'J' => Tile::NorthWest,
+            '7' => Tile::SouthWest,
+            'F' => Tile::SouthEast,
+            _ => Tile::Ground,
+        }
+    }
+
+    pub(crate) fn connections(&self) -> Option<[Direction; 2]> {
+        match self {
+            Tile::Vertical => Some([Direction::North, Direction::South]),
+            Tile::Horizontal => Some([Direction::East, Direction::West]),
+            Tile::NorthEast => Some([Direction::North, Direction::East]),
+            Tile::NorthWest => Some([Direction::North, Direction::West]),
+            Tile::SouthWest => Some([Direction::South, Direction::West]),
+            Tile::SouthEast => Some([Direction::South, Direction::East]),
+            Tile::Ground => None,
+        }
+    }
+
+    pub(crate) fn connects(&self, direction: Direction) -> bool {
+        self.connections()
+            .is_some_and(|connections| connections.contains(&direction))
+    }
+
+    fn from_connections(first: Direction, second: Direction) -> Tile {
+        Tile::PIPES
+            .into_iter()
+            .find(|tile| tile.connects(first) && tile.connects(second))
+            .unwrap()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Maze {
+    tiles: Vec<Vec<Tile>>,
+    start: (usize, usize),
+}
+
+impl Maze {
+    // Panics with the position of the problem on malformed input.
+    pub(crate) fn parse(input: &str) -> Maze {
+        let (rows, start) =
+            parse(grammar::maze(), input).unwrap_or_else(|error| panic!("{}", error));
+        let tiles = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Tile::from_char).collect())
+            .collect();
+        let mut maze = Maze { tiles, start };
+        let start_tile = maze.infer_start_tile();
+        maze.tiles[maze.start.1][maze.start.0] = start_tile;
+        maze
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.tiles.first().map_or(0, |row| row.len())
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub(crate) fn start(&self) -> (usize, usize) {
+        self.start
+    }
+
+    pub(crate) fn tile(&self, position: (usize, usize)) -> Tile {
+        self.tiles[position.1][position.0]
+    }
+
+    fn neighbour(&self, position: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.offset();
+        let x = position.0.checked_add_signed(dx)?;
+        let y = position.1.checked_add_signed(dy)?;
+        let row = self.tiles.get(y)?;
+        if x < row.len() {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    // The pipe under S has to connect to exactly the neighbours that point back at it.
+    // When more than two neighbours do, the pair that closes a loop wins.
+    fn infer_start_tile(&self) -> Tile {
+        let candidates = Direction::ALL
+            .into_iter()
+            .filter(|direction| {
+                self.neighbour(self.start, *direction)
+                    .is_some_and(|n| self.tile(n).connects(direction.opposite()))
+            })
+            .collect::<Vec<Direction>>();
+        for (i, first) in candidates.iter().enumerate() {
+            for second in &candidates[i + 1..] {
+                let tile = Tile::from_connections(*first, *second);
+                if self.walk(tile).is_some() {
+                    return tile;
+                }
+            }
+        }
+        panic!("S at {:?} is not part of a loop", self.start);
+    }
+
+    // Follows the pipes from S, assuming S is `start_tile`, until it comes back to S.
+    fn walk(&self, start_tile: Tile) -> Option<Vec<(usize, usize)>> {
+        let mut pipe_loop = vec![self.start];
+        let mut heading = start_tile.connections()?[0];
+        let mut position = self.start;
+        loop {
+            position = self.neighbour(position, heading)?;
+            if position == self.start {
+                let entered_from = heading.opposite();
+                return if start_tile.connects(entered_from) {
+                    Some(pipe_loop)
+                } else {
+                    None
+                };
+            }
+            let entered_from = heading.opposite();
+            let connections = self.tile(position).connections()?;
+            if !connections.contains(&entered_from) {
+                return None;
+            }
+            heading = if connections[0] == entered_from {
+                connections[1]
+            } else {
+                connections[0]
+            };
+            pipe_loop.push(position);
+        }
+    }
+
+    // Tiles of the main loop in walking order, starting at S.
+    pub(crate) fn main_loop(&self) -> Vec<(usize, usize)> {
+        self.walk(self.tile(self.start)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_start_tile() {
+        let maze = Maze::parse(
+            ".....
+.S-7.
+.|.|.
+.L-J.
+.....",
+        );
+        assert_eq!(maze.start(), (1, 1));
+        assert_eq!(maze.tile((1, 1)), Tile::SouthEast);
+
+        let maze = Maze::parse(
+            "7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ",
+        );
+        assert_eq!(maze.tile(maze.start()), Tile::SouthEast);
+    }
+
+    #[test]
+    fn test_infer_start_tile_with_extra_neighbours() {
+        // Every neighbour of S points at it, only east and south close the loop.
+        let maze = Maze::parse(
+            ".|..
+-S7.
+.LJ.",
+        );
+        assert_eq!(maze.tile(maze.start()), Tile::SouthEast);
+        assert_eq!(maze.main_loop().len(), 4);
+    }
+
+    #[test]
+    fn test_main_loop() {
+        let maze = Maze::parse(
+            "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        );
+        assert_eq!(
+            maze.main_loop(),
+            vec![
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 3),
+                (3, 3),
+                (3, 2),
+                (3, 1),
+                (2, 1)
+            ]
+        );
+    }
+}
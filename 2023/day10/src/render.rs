@@ -0,0 +1,126 @@
+use crate::maze::{Direction, Maze, Tile};
+
+const INSIDE: char = '●';
+const OUTSIDE: char = '·';
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Style {
+    Plain,
+    Ansi,
+}
+
+fn box_char(tile: Tile) -> char {
+    match tile {
+        Tile::Vertical => '│',
+        Tile::Horizontal => '─',
+        Tile::NorthEast => '└',
+        Tile::NorthWest => '┘',
+        Tile::SouthWest => '┐',
+        Tile::SouthEast => '┌',
+        Tile::Ground => ' ',
+    }
+}
+
+fn paint(c: char, colour: &str, style: Style) -> String {
+    match style {
+        Style::Plain => c.to_string(),
+        Style::Ansi => format!("\x1b[{}m{}\x1b[0m", colour, c),
+    }
+}
+
+// Redraws the maze with the main loop in box-drawing characters. Pipes that are not
+// part of the loop are treated as ground, and every ground tile is marked as enclosed
+// or outside. A tile is enclosed when a scan from the left edge has crossed an odd
+// number of loop pipes that reach north.
+pub(crate) fn render(maze: &Maze, style: Style) -> String {
+    let mut on_loop = vec![vec![false; maze.width()]; maze.height()];
+    for (x, y) in maze.main_loop() {
+        on_loop[y][x] = true;
+    }
+
+    let mut lines = vec![];
+    for (y, row) in on_loop.iter().enumerate() {
+        let mut inside = false;
+        let mut line = String::new();
+        for (x, is_loop) in row.iter().enumerate() {
+            let tile = maze.tile((x, y));
+            if *is_loop {
+                if tile.connects(Direction::North) {
+                    inside = !inside;
+                }
+                let colour = if (x, y) == maze.start() { "1;31" } else { "1;33" };
+                line.push_str(&paint(box_char(tile), colour, style));
+            } else if inside {
+                line.push_str(&paint(INSIDE, "32", style));
+            } else {
+                line.push_str(&paint(OUTSIDE, "2", style));
+            }
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_simple_loop() {
+        let maze = Maze::parse(
+            "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        );
+        let expected = "·····
+·┌─┐·
+·│●│·
+·└─┘·
+·····";
+        assert_eq!(render(&maze, Style::Plain), expected);
+    }
+
+    #[test]
+    fn test_render_enclosed_tiles() {
+        let maze = Maze::parse(
+            "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        );
+        let expected = "··········
+·┌──────┐·
+·│┌────┐│·
+·││····││·
+·││····││·
+·│└─┐┌─┘│·
+·│●●││●●│·
+·└──┘└──┘·
+··········";
+        let rendered = render(&maze, Style::Plain);
+        assert_eq!(rendered, expected);
+        assert_eq!(rendered.matches(INSIDE).count(), 4);
+    }
+
+    #[test]
+    fn test_render_ansi() {
+        let maze = Maze::parse(
+            ".....
+.S-7.
+.|.|.
+.L-J.
+.....",
+        );
+        let rendered = render(&maze, Style::Ansi);
+        assert!(rendered.starts_with("\x1b[2m·\x1b[0m"));
+        assert!(rendered.contains("\x1b[1;31m┌\x1b[0m"));
+        assert!(rendered.contains("\x1b[32m●\x1b[0m"));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::grammar;
+use common::parse::parse;
+
+// Galaxy positions with, for every row and column, how many empty ones come before
+// it. Expanding the universe by any factor is then a lookup per coordinate instead of
+// a rebuilt grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Universe {
+    galaxies: Vec<(usize, usize)>,
+    empty_rows_before: Vec<u64>,
+    empty_columns_before: Vec<u64>,
+}
+
+// Prefix sums over `occupied`: entry i counts the unoccupied lines before line i.
+fn empty_before(occupied: &[bool]) -> Vec<u64> {
+    occupied
+        .iter()
+        .scan(0, |empty, occupied| {
+            let before = *empty;
+            *empty += !occupied as u64;
+            Some(before)
+        })
+        .collect()
+}
+
+// Sum of |a - b| over all pairs, from the sorted values: the i-th smallest value is
+// larger than the i values before it.
+fn pairwise_distance_sum(mut values: Vec<u64>) -> u128 {
+    values.sort_unstable();
+    let mut prefix = 0u128;
+    let mut sum = 0u128;
+    for (index, value) in values.into_iter().enumerate() {
+        sum += value as u128 * index as u128 - prefix;
+        prefix += value as u128;
+    }
+    sum
+}
+
+impl Universe {
+    // Panics with the position of the problem on malformed input.
+    pub(crate) fn parse(input: &str) -> Universe {
+        let image = parse(grammar::image(), input).unwrap_or_else(|error| panic!("{}", error));
+        let width = image.first().map_or(0, Vec::len);
+        let galaxies = image
+            .iter()
+            .enumerate()
+            .flat_map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, galaxy)| **galaxy)
+                    .map(move |(column, _)| (row, column))
+            })
+            .collect::<Vec<(usize, usize)>>();
+        let mut occupied_rows = vec![false; image.len()];
+        let mut occupied_columns = vec![false; width];
+        for (row, column) in &galaxies {
+            occupied_rows[*row] = true;
+            occupied_columns[*column] = true;
+        }
+        Universe {
+            galaxies,
+            empty_rows_before: empty_before(&occupied_rows),
+            empty_columns_before: empty_before(&occupied_columns),
+        }
+    }
+
+    // Every empty row and column becomes `factor` of them: 2 for part 1, a million for
+    // part 2, 1 for the unexpanded image.
+    pub(crate) fn expanded(&self, factor: u64) -> Vec<(u64, u64)> {
+        let grow =
+            |line: usize, empty_before: u64| line as u64 - empty_before + empty_before * factor;
+        self.galaxies
+            .iter()
+            .map(|(row, column)| {
+                (
+                    grow(*row, self.empty_rows_before[*row]),
+                    grow(*column, self.empty_columns_before[*column]),
+                )
+            })
+            .collect()
+    }
+
+    // Sum of the Manhattan distances between every pair of galaxies. The two axes are
+    // independent, so each is sorted and summed on its own in O(n log n).
+    pub(crate) fn distance_sum(&self, factor: u64) -> u128 {
+        let (rows, columns) = self.expanded(factor).into_iter().unzip();
+        pairwise_distance_sum(rows) + pairwise_distance_sum(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
+
+    #[test]
+    fn test_expanded() {
+        let universe = Universe::parse(EXAMPLE);
+        let expanded = universe.expanded(2);
+        assert_eq!(expanded[0], (0, 4));
+        assert_eq!(expanded[4], (6, 1));
+        assert_eq!(expanded[8], (11, 5));
+        assert_eq!(universe.expanded(1)[8], (9, 4));
+    }
+
+    #[test]
+    fn test_distance_sum() {
+        let universe = Universe::parse(EXAMPLE);
+        assert_eq!(universe.distance_sum(2), 374);
+        assert_eq!(universe.distance_sum(10), 1030);
+        assert_eq!(universe.distance_sum(100), 8410);
+    }
+
+    #[test]
+    fn test_matches_pairwise() {
+        let universe = Universe::parse(EXAMPLE);
+        for factor in [0, 1, 2, 7, 1_000_000] {
+            let galaxies = universe.expanded(factor);
+            let mut expected = 0;
+            for (index, a) in galaxies.iter().enumerate() {
+                for b in &galaxies[index + 1..] {
+                    expected += (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u128;
+                }
+            }
+            assert_eq!(universe.distance_sum(factor), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2, column 3: expected # or ., found \"x\"")]
+    fn test_malformed_image() {
+        Universe::parse("#..\n..x\n");
+    }
+}
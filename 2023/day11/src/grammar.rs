@@ -0,0 +1,10 @@
+use common::parse::{grid, Parser};
+
+// `#` for a galaxy, `.` for empty space.
+pub(crate) fn image<'a>() -> impl Parser<'a, Vec<Vec<bool>>> {
+    grid("# or .", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
@@ -1,3 +1,5 @@
+mod galaxy;
+mod grammar;
 mod part1;
 mod part2;
 
@@ -0,0 +1,28 @@
+use common::parse::{integer, lines, spaces1, tag, take_while1, Parser};
+
+// `???.### 1,1,3`: the condition record and the sizes of its damaged groups.
+pub(crate) fn record<'a>() -> impl Parser<'a, (&'a str, Vec<usize>)> {
+    take_while1("spring", |c| ".#?".contains(c))
+        .then_ignore(spaces1())
+        .then(integer().separated_by(tag(",")))
+}
+
+pub(crate) fn records<'a>() -> impl Parser<'a, Vec<(&'a str, Vec<usize>)>> {
+    lines(record())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_records() {
+        assert_eq!(
+            parse(records(), "???.### 1,1,3\n.#?. 2\n"),
+            Ok(vec![("???.###", vec![1, 1, 3]), (".#?.", vec![2])])
+        );
+        let error = parse(records(), "???.### 1,1,3\n.#x. 2").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
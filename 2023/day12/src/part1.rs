@@ -1,3 +1,6 @@
+use crate::grammar;
+use common::parse::parse;
+
 fn powerset<T>(s: &[T]) -> Vec<Vec<&T>> {
     (0..2usize.pow(s.len() as u32)).map(|i| {
         s.iter().enumerate().filter(|&(t, _)| (i >> t) % 2 == 1)
@@ -72,21 +75,12 @@ pub fn run() {
 }
 
 fn parse_input(input: &str) -> Vec<Record> {
-    input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let literal = parts.next().unwrap();
-            let shape = parts
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>();
-            Record {
-                literal: literal.to_string(),
-                shape
-            }
+    parse(grammar::records(), input)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .into_iter()
+        .map(|(literal, shape)| Record {
+            literal: literal.to_string(),
+            shape
         })
         .collect::<Vec<Record>>()
 }
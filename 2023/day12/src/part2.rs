@@ -1,3 +1,6 @@
+use crate::grammar;
+use common::parse::parse;
+
 fn powerset<T>(s: &[T]) -> Vec<Vec<&T>> {
     (0..2usize.pow(s.len() as u32)).map(|i| {
         s.iter().enumerate().filter(|&(t, _)| (i >> t) % 2 == 1)
@@ -75,8 +78,11 @@ impl Record {
 fn check_posibility(test_literal: &mut String, test_index: &usize, shape: &Vec<usize>, test_char: &str) -> bool {
     let mut changed_literal = test_literal.clone();
     changed_literal.replace_range(test_index..&(test_index + 1), test_char);
-    
-    
+    let record = Record {
+        literal: changed_literal,
+        shape: shape.clone()
+    };
+    !record.get_valid_completeds().is_empty()
 }
 
 fn get_shape(literal: &str) -> Vec<usize> {
@@ -127,21 +133,12 @@ pub fn run() {
 }
 
 fn parse_input(input: &str) -> Vec<Record> {
-    input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-            let literal = parts.next().unwrap();
-            let shape = parts
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|s| s.parse::<usize>().unwrap())
-                .collect::<Vec<usize>>();
-            Record {
-                literal: literal.to_string(),
-                shape
-            }
+    parse(grammar::records(), input)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .into_iter()
+        .map(|(literal, shape)| Record {
+            literal: literal.to_string(),
+            shape
         })
         .collect::<Vec<Record>>()
 }
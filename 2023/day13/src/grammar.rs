@@ -0,0 +1,68 @@
+use crate::mirror::Pattern;
+use common::parse::{grid, sections, Parser};
+
+// Rows of `#` and `.`, at most 64 columns wide and 64 rows tall.
+pub(crate) fn pattern<'a>() -> impl Parser<'a, Pattern> {
+    let cells = grid("# or .", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    });
+    cells.try_map(
+        |cells| match (cells.first().map_or(0, Vec::len), cells.len()) {
+            (width, _) if width > 64 => {
+                Err(format!("pattern of at most 64 columns, not {}", width))
+            }
+            (_, height) if height > 64 => {
+                Err(format!("pattern of at most 64 rows, not {}", height))
+            }
+            _ => Ok(Pattern::from_cells(&cells)),
+        },
+    )
+}
+
+pub(crate) fn patterns<'a>() -> impl Parser<'a, Vec<Pattern>> {
+    sections(pattern())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mirror::Axis;
+    use common::parse::parse;
+
+    #[test]
+    fn test_patterns() {
+        let parsed = parse(patterns(), "#.\n.#\n\n##\n").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                Pattern::from_cells(&[vec![true, false], vec![false, true]]),
+                Pattern::from_cells(&[vec![true, true]])
+            ]
+        );
+        let error = parse(patterns(), "#.\n.#\n\n#\n##").unwrap_err();
+        assert_eq!(error.line, 5);
+        assert_eq!(error.expected, vec!["row of 1 cells, not 2".to_string()]);
+        let error = parse(pattern(), &"#".repeat(65)).unwrap_err();
+        assert_eq!(
+            error.expected,
+            vec!["pattern of at most 64 columns, not 65".to_string()]
+        );
+        let error = parse(pattern(), &"#\n".repeat(65)).unwrap_err();
+        assert_eq!(
+            error.expected,
+            vec!["pattern of at most 64 rows, not 65".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_tallest_pattern() {
+        // Transposing turns the 64 rows into 64 bits per column.
+        let parsed = parse(pattern(), &"#..#\n".repeat(64)).unwrap();
+        assert_eq!(parsed.transpose().transpose(), parsed);
+        let mirrors = parsed.reflections(Axis::Vertical, 0);
+        assert_eq!(mirrors.len(), 1);
+        assert_eq!(mirrors[0].position, 2);
+    }
+}
@@ -1,7 +1,27 @@
+mod grammar;
+mod mirror;
 mod part1;
 mod part2;
 
 fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    // `cargo run -- --smudges` lists where each pattern's smudge is.
+    if args.iter().any(|arg| arg == "--smudges") {
+        let input = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+        for (index, pattern) in mirror::parse_patterns(&input).iter().enumerate() {
+            match pattern.all_reflections(1).first() {
+                Some(reflection) => println!(
+                    "Pattern {}: {:?} mirror at {}, smudge at {:?}",
+                    index + 1,
+                    reflection.axis,
+                    reflection.position,
+                    reflection.mismatches[0]
+                ),
+                None => println!("Pattern {}: no smudged mirror", index + 1),
+            }
+        }
+        return;
+    }
     part1::run();
     part2::run();
 }
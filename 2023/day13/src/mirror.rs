@@ -0,0 +1,235 @@
+use crate::grammar;
+use common::parse::parse;
+
+// A pattern of ash (.) and rocks (#), one u64 per row with column c in bit c, so
+// comparing two rows is a xor and a popcount. `transpose` packs columns the same way,
+// so neither side may exceed 64 cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Pattern {
+    rows: Vec<u64>,
+    width: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Axis {
+    // A mirror between two columns.
+    Vertical,
+    // A mirror between two rows.
+    Horizontal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Reflection {
+    pub(crate) axis: Axis,
+    // Columns left of (or rows above) the mirror.
+    pub(crate) position: usize,
+    // (row, column) cells that differ from their mirror image, each reported once on
+    // the side closer to the top left.
+    pub(crate) mismatches: Vec<(usize, usize)>,
+}
+
+impl Reflection {
+    // The puzzle's summary: columns left of a vertical mirror, 100 times the rows above
+    // a horizontal one.
+    pub(crate) fn score(&self) -> usize {
+        match self.axis {
+            Axis::Vertical => self.position,
+            Axis::Horizontal => 100 * self.position,
+        }
+    }
+}
+
+impl Pattern {
+    pub(crate) fn from_cells(cells: &[Vec<bool>]) -> Pattern {
+        let rows = cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .fold(0, |bits, (column, rock)| bits | (*rock as u64) << column)
+            })
+            .collect();
+        Pattern {
+            rows,
+            width: cells.first().map_or(0, Vec::len),
+        }
+    }
+
+    pub(crate) fn transpose(&self) -> Pattern {
+        let rows = (0..self.width)
+            .map(|column| {
+                self.rows
+                    .iter()
+                    .enumerate()
+                    .fold(0, |transposed, (row, bits)| {
+                        transposed | ((bits >> column) & 1) << row
+                    })
+            })
+            .collect();
+        Pattern {
+            rows,
+            width: self.rows.len(),
+        }
+    }
+
+    // Horizontal mirrors with exactly `k` mismatched cells, as (position, mismatches).
+    fn row_reflections(&self, k: usize) -> Vec<(usize, Vec<(usize, usize)>)> {
+        (1..self.rows.len())
+            .filter_map(|position| {
+                let pairs = (0..position)
+                    .rev()
+                    .zip(position..self.rows.len())
+                    .map(|(above, below)| (above, self.rows[above] ^ self.rows[below]));
+                let mut count = 0;
+                let mut mismatches = vec![];
+                for (row, difference) in pairs {
+                    count += difference.count_ones() as usize;
+                    if count > k {
+                        return None;
+                    }
+                    mismatches.extend(
+                        (0..self.width)
+                            .filter(|column| difference >> column & 1 == 1)
+                            .map(|column| (row, column)),
+                    );
+                }
+                (count == k).then_some((position, mismatches))
+            })
+            .collect()
+    }
+
+    // Every mirror along `axis` whose two halves differ in exactly `k` cells: 0 for a
+    // perfect reflection, 1 for a single smudge.
+    pub(crate) fn reflections(&self, axis: Axis, k: usize) -> Vec<Reflection> {
+        let found = match axis {
+            Axis::Horizontal => self.row_reflections(k),
+            Axis::Vertical => self
+                .transpose()
+                .row_reflections(k)
+                .into_iter()
+                .map(|(position, mismatches)| {
+                    let mismatches = mismatches.into_iter().map(|(c, r)| (r, c)).collect();
+                    (position, mismatches)
+                })
+                .collect(),
+        };
+        found
+            .into_iter()
+            .map(|(position, mismatches)| Reflection {
+                axis,
+                position,
+                mismatches,
+            })
+            .collect()
+    }
+
+    // Vertical mirrors first, then horizontal ones.
+    pub(crate) fn all_reflections(&self, k: usize) -> Vec<Reflection> {
+        let mut reflections = self.reflections(Axis::Vertical, k);
+        reflections.extend(self.reflections(Axis::Horizontal, k));
+        reflections
+    }
+}
+
+// Patterns are separated by blank lines.
+pub(crate) fn parse_patterns(input: &str) -> Vec<Pattern> {
+    parse(grammar::patterns(), input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+// The puzzle answer: the score of the first mirror of each pattern, 0 for none.
+pub(crate) fn summarize(input: &str, k: usize) -> usize {
+    parse_patterns(input)
+        .iter()
+        .map(|pattern| {
+            pattern
+                .all_reflections(k)
+                .first()
+                .map_or(0, Reflection::score)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(text: &str) -> Pattern {
+        parse(grammar::pattern(), text).unwrap()
+    }
+
+    const FIRST: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.";
+
+    const SECOND: &str = "#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+    #[test]
+    fn test_transpose() {
+        let pattern = read("#..\n.##");
+        assert_eq!(pattern.transpose(), read("#.\n.#\n.#"));
+        assert_eq!(read(FIRST).transpose().transpose(), read(FIRST));
+    }
+
+    #[test]
+    fn test_exact_reflections() {
+        let first = read(FIRST);
+        assert_eq!(
+            first.all_reflections(0),
+            vec![Reflection {
+                axis: Axis::Vertical,
+                position: 5,
+                mismatches: vec![]
+            }]
+        );
+        let second = read(SECOND);
+        assert_eq!(second.reflections(Axis::Vertical, 0), vec![]);
+        assert_eq!(second.reflections(Axis::Horizontal, 0)[0].score(), 400);
+    }
+
+    #[test]
+    fn test_smudges() {
+        // The puzzle's smudges: (0, 0) in the first pattern, and (1, 4) in the second,
+        // which is reported as its mirror image (0, 4).
+        let first = read(FIRST).all_reflections(1);
+        assert_eq!(
+            first,
+            vec![Reflection {
+                axis: Axis::Horizontal,
+                position: 3,
+                mismatches: vec![(0, 0)]
+            }]
+        );
+        let second = read(SECOND).all_reflections(1);
+        assert_eq!(second[0].position, 1);
+        assert_eq!(second[0].mismatches, vec![(0, 4)]);
+    }
+
+    #[test]
+    fn test_more_mismatches() {
+        let pattern = read("#.#\n...\n#..");
+        let vertical = pattern.reflections(Axis::Vertical, 2);
+        assert_eq!(vertical.len(), 1);
+        assert_eq!(vertical[0].mismatches, vec![(0, 0), (2, 0)]);
+        let horizontal = pattern.reflections(Axis::Horizontal, 2);
+        assert_eq!(horizontal[0].position, 1);
+        assert_eq!(horizontal[0].mismatches, vec![(0, 0), (0, 2)]);
+        assert_eq!(pattern.all_reflections(3), vec![]);
+    }
+
+    #[test]
+    fn test_summarize() {
+        let input = format!("{}\n\n{}\n", FIRST, SECOND);
+        assert_eq!(summarize(&input, 0), 405);
+        assert_eq!(summarize(&input, 1), 400);
+    }
+}
@@ -0,0 +1,6 @@
+use common::parse::{grid, Parser};
+
+// Round rocks `O`, cube rocks `#` and empty space `.`.
+pub(crate) fn platform<'a>() -> impl Parser<'a, Vec<Vec<char>>> {
+    grid("O, # or .", |c| "O#.".contains(c).then_some(c))
+}
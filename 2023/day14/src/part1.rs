@@ -1,3 +1,5 @@
+use crate::grammar;
+use common::parse::parse;
 use ndarray::Array2;
 
 pub fn run() {
@@ -18,10 +20,9 @@ fn evaluate(input: &str) -> usize {
 }
 
 fn str_to_matrix(pattern: &str) -> Array2<char> {
-    let rows = pattern.lines().count();
-    let cols = pattern.lines().next().unwrap().chars().count();
-    let parsed_chars = pattern.replace("\n", "").chars().collect::<Vec<char>>();
-    Array2::from_shape_vec((rows, cols), parsed_chars).unwrap()
+    let rows = parse(grammar::platform(), pattern).unwrap_or_else(|error| panic!("{}", error));
+    let shape = (rows.len(), rows.first().map_or(0, Vec::len));
+    Array2::from_shape_vec(shape, rows.concat()).unwrap()
 }
 
 fn get_column_load(column: &[char]) -> usize {
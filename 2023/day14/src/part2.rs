@@ -1,3 +1,5 @@
+use crate::grammar;
+use common::parse::parse;
 use ndarray::{Array1, Array2, ArrayView1};
 use indicatif::ProgressIterator;
 
@@ -107,10 +109,9 @@ fn tilt_array_reversed(array: &ArrayView1<char>) -> Array1<char> {
 }
 
 fn str_to_matrix(pattern: &str) -> Array2<char> {
-    let rows = pattern.lines().count();
-    let cols = pattern.lines().next().unwrap().chars().count();
-    let parsed_chars = pattern.replace("\n", "").chars().collect::<Vec<char>>();
-    Array2::from_shape_vec((rows, cols), parsed_chars).unwrap()
+    let rows = parse(grammar::platform(), pattern).unwrap_or_else(|error| panic!("{}", error));
+    let shape = (rows.len(), rows.first().map_or(0, Vec::len));
+    Array2::from_shape_vec(shape, rows.concat()).unwrap()
 }
 
 #[cfg(test)]
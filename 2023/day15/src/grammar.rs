@@ -0,0 +1,54 @@
+use crate::hashmap::Op;
+use common::parse::{integer, newline, tag, take_while1, Parser};
+
+// A step's raw text, as hashed in part 1.
+pub(crate) fn step<'a>() -> impl Parser<'a, &'a str> {
+    take_while1("step", |c| !matches!(c, ',' | '\r' | '\n'))
+}
+
+// `rn=1` or `cm-`.
+pub(crate) fn op<'a>() -> impl Parser<'a, Op<usize>> {
+    let label = take_while1("label", |c| c.is_ascii_alphabetic()).map(str::to_string);
+    let operation = tag("-")
+        .to(None)
+        .or(tag("=").ignore_then(integer()).map(Some));
+    label.then(operation).map(|(label, value)| match value {
+        Some(value) => Op::Insert(label, value),
+        None => Op::Remove(label),
+    })
+}
+
+// The comma separated initialization sequence; line breaks around commas are ignored.
+pub(crate) fn sequence<'a, T>(step: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    let comma = newline()
+        .optional()
+        .ignore_then(tag(","))
+        .then_ignore(newline().optional());
+    step.separated_by(comma)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_sequence() {
+        assert_eq!(
+            parse(sequence(step()), "rn=1,cm-\n,qp=3\n"),
+            Ok(vec!["rn=1", "cm-", "qp=3"])
+        );
+        assert_eq!(
+            parse(sequence(op()), "rn=1,cm-"),
+            Ok(vec![
+                Op::Insert("rn".to_string(), 1),
+                Op::Remove("cm".to_string())
+            ])
+        );
+        let error = parse(sequence(op()), "rn=1,cm=x").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected),
+            (9, vec!["number".to_string()])
+        );
+    }
+}
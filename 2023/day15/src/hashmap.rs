@@ -0,0 +1,213 @@
+use crate::grammar;
+use common::parse::{parse, ParseError};
+use std::fmt;
+
+const BOX_COUNT: usize = 256;
+
+// The puzzle's HASH algorithm: a label's box number.
+pub(crate) fn hash(input: &str) -> usize {
+    input
+        .bytes()
+        .fold(0, |result, c| (result + c as usize) * 17 % BOX_COUNT)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Op<V> {
+    // `label-`
+    Remove(String),
+    // `label=value`
+    Insert(String, V),
+}
+
+// Back to the step's own text, e.g. `rn=1`.
+impl<V: fmt::Display> fmt::Display for Op<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Remove(label) => write!(f, "{}-", label),
+            Op::Insert(label, value) => write!(f, "{}={}", label, value),
+        }
+    }
+}
+
+// The comma separated initialization sequence.
+pub(crate) fn parse_steps(input: &str) -> Result<Vec<Op<usize>>, ParseError> {
+    parse(grammar::sequence(grammar::op()), input)
+}
+
+// HASHMAP: 256 boxes picked by `hash`, each keeping its entries in insertion order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Dictionary<V> {
+    boxes: Vec<Vec<(String, V)>>,
+}
+
+impl<V> Dictionary<V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            boxes: (0..BOX_COUNT).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    // Replaces the value in place if the label is already in its box, otherwise
+    // appends it. Returns the old value.
+    pub(crate) fn insert(&mut self, label: String, value: V) -> Option<V> {
+        let entries = &mut self.boxes[hash(&label)];
+        match entries.iter_mut().find(|(l, _)| *l == label) {
+            Some((_, existing)) => Some(std::mem::replace(existing, value)),
+            None => {
+                entries.push((label, value));
+                None
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, label: &str) -> Option<V> {
+        let entries = &mut self.boxes[hash(label)];
+        let position = entries.iter().position(|(l, _)| l == label)?;
+        Some(entries.remove(position).1)
+    }
+
+    pub(crate) fn get(&self, label: &str) -> Option<&V> {
+        self.boxes[hash(label)]
+            .iter()
+            .find(|(l, _)| l == label)
+            .map(|(_, value)| value)
+    }
+
+    // Box numbers with their entries, in box order, including empty boxes.
+    pub(crate) fn iter_boxes(&self) -> impl Iterator<Item = (usize, &[(String, V)])> {
+        self.boxes
+            .iter()
+            .enumerate()
+            .map(|(index, entries)| (index, entries.as_slice()))
+    }
+
+    pub(crate) fn handle(&mut self, op: Op<V>) {
+        match op {
+            Op::Remove(label) => {
+                self.remove(&label);
+            }
+            Op::Insert(label, value) => {
+                self.insert(label, value);
+            }
+        }
+    }
+
+    // Sum of (box number + 1) * (slot + 1) * strength, with the strength of each value
+    // given by `strength`.
+    pub(crate) fn focusing_power<F: Fn(&V) -> usize>(&self, strength: F) -> usize {
+        self.iter_boxes()
+            .flat_map(|(box_index, entries)| {
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(move |(slot, (_, value))| (box_index, slot, value))
+            })
+            .map(|(box_index, slot, value)| (box_index + 1) * (slot + 1) * strength(value))
+            .sum()
+    }
+}
+
+impl<V> Default for Dictionary<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Extend<(String, V)> for Dictionary<V> {
+    fn extend<I: IntoIterator<Item = (String, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(label, value)| {
+            self.insert(label, value);
+        });
+    }
+}
+
+impl<V> Extend<Op<V>> for Dictionary<V> {
+    fn extend<I: IntoIterator<Item = Op<V>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|op| self.handle(op));
+    }
+}
+
+impl<V> FromIterator<(String, V)> for Dictionary<V> {
+    fn from_iter<I: IntoIterator<Item = (String, V)>>(iter: I) -> Self {
+        let mut dictionary = Dictionary::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+impl<V> FromIterator<Op<V>> for Dictionary<V> {
+    fn from_iter<I: IntoIterator<Item = Op<V>>>(iter: I) -> Self {
+        let mut dictionary = Dictionary::new();
+        dictionary.extend(iter);
+        dictionary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_op(step: &str) -> Result<Op<usize>, ParseError> {
+        parse(grammar::op(), step)
+    }
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash("rn"), 0);
+        assert_eq!(hash("qp"), 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(read_op("rn=1"), Ok(Op::Insert("rn".to_string(), 1)));
+        assert_eq!(read_op("cm-"), Ok(Op::Remove("cm".to_string())));
+        let error = read_op("cm").unwrap_err();
+        assert_eq!(error.column, 3);
+        assert_eq!(
+            error.expected,
+            vec!["\"-\"".to_string(), "\"=\"".to_string()]
+        );
+        assert_eq!(read_op("cm=x").unwrap_err().column, 4);
+        assert_eq!(read_op("cm-3").unwrap_err().found, "\"3\"");
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut dictionary = Dictionary::new();
+        assert_eq!(dictionary.insert("rn".to_string(), "one"), None);
+        assert_eq!(dictionary.insert("cm".to_string(), "two"), None);
+        assert_eq!(dictionary.insert("rn".to_string(), "three"), Some("one"));
+        assert_eq!(dictionary.get("rn"), Some(&"three"));
+        assert_eq!(dictionary.remove("rn"), Some("three"));
+        assert_eq!(dictionary.remove("rn"), None);
+        assert_eq!(dictionary.get("rn"), None);
+        let (_, box_zero) = dictionary.iter_boxes().next().unwrap();
+        assert_eq!(box_zero, &[("cm".to_string(), "two")]);
+    }
+
+    #[test]
+    fn test_collect_ops() {
+        let steps = parse_steps("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7").unwrap();
+        let dictionary = steps.into_iter().collect::<Dictionary<usize>>();
+        let non_empty = dictionary
+            .iter_boxes()
+            .filter(|(_, entries)| !entries.is_empty())
+            .map(|(index, entries)| (index, entries.len()))
+            .collect::<Vec<(usize, usize)>>();
+        assert_eq!(non_empty, vec![(0, 2), (3, 3)]);
+        assert_eq!(dictionary.focusing_power(|focal_length| *focal_length), 145);
+    }
+
+    #[test]
+    fn test_from_pairs() {
+        let dictionary = [("a".to_string(), 2.5), ("b".to_string(), 4.0)]
+            .into_iter()
+            .collect::<Dictionary<f64>>();
+        assert_eq!(dictionary.get("b"), Some(&4.0));
+        assert_eq!(
+            dictionary.focusing_power(|value| value.round() as usize),
+            (hash("a") + 1) * 3 + (hash("b") + 1) * 4
+        );
+    }
+}
@@ -1,7 +1,41 @@
+mod grammar;
+mod hashmap;
 mod part1;
 mod part2;
+mod trace;
+
+use common::cli::usage_error;
 
 fn main() {
     part1::run();
     part2::run();
+
+    // `cargo run -- --dump` prints the boxes after every step, `--stop-after 10` stops
+    // early and `--json boxes.json` writes the final non-empty boxes
+    let args = std::env::args().collect::<Vec<String>>();
+    let usage = "cargo run -- [--dump] [--stop-after <steps>] [--json <output.json>]";
+    let flag_value = |flag: &str| {
+        args.iter().position(|arg| arg == flag).map(|position| {
+            args.get(position + 1)
+                .cloned()
+                .unwrap_or_else(|| usage_error(usage))
+        })
+    };
+    let dump = args.iter().any(|arg| arg == "--dump");
+    let stop_after = flag_value("--stop-after")
+        .map(|n| n.parse::<usize>().unwrap_or_else(|_| usage_error(usage)));
+    let json_path = flag_value("--json");
+    if dump || stop_after.is_some() || json_path.is_some() {
+        let input = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+        let steps = hashmap::parse_steps(&input).unwrap();
+        let dictionary = if dump {
+            trace::replay(steps, stop_after, std::io::stdout().lock()).unwrap()
+        } else {
+            trace::replay(steps, stop_after, std::io::sink()).unwrap()
+        };
+        if let Some(json_path) = json_path {
+            std::fs::write(&json_path, trace::json_snapshot(&dictionary)).unwrap();
+            println!("Box snapshot written to {}", json_path);
+        }
+    }
 }
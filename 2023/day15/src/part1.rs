@@ -1,3 +1,7 @@
+use crate::grammar;
+use crate::hashmap::hash;
+use common::parse::parse;
+
 pub fn run() {
     let input_path = "src/inputs/input.txt";
     let input = std::fs::read_to_string(input_path).unwrap();
@@ -6,17 +10,11 @@ pub fn run() {
 }
 
 fn evaluate(input: &str) -> usize {
-    input.replace("\n", "").split(",").map(|s| hash(s)).sum()
-}
-
-fn hash(input: &str) -> usize {
-    let mut result = 0;
-    input.chars().for_each(|c| {
-        result = result + c as usize;
-        result *= 17;
-        result %= 256;
-    });
-    result
+    parse(grammar::sequence(grammar::step()), input)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .into_iter()
+        .map(hash)
+        .sum()
 }
 
 #[cfg(test)]
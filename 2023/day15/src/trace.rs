@@ -0,0 +1,162 @@
+use crate::hashmap::{Dictionary, Op};
+use std::fmt::{Display, Write};
+
+// The non-empty boxes in the puzzle's own notation, one per line:
+// `Box 0: [rn 1] [cm 2]`.
+pub(crate) fn format_boxes<V: Display>(dictionary: &Dictionary<V>) -> String {
+    let mut output = String::new();
+    for (index, entries) in dictionary.iter_boxes().filter(|(_, e)| !e.is_empty()) {
+        write!(output, "Box {}:", index).unwrap();
+        for (label, value) in entries {
+            write!(output, " [{} {}]", label, value).unwrap();
+        }
+        output.push('\n');
+    }
+    output
+}
+
+// Applies the steps one at a time, printing the boxes after each like the puzzle's
+// walkthrough does, and noting the lens a step replaces or takes out. With
+// `stop_after` only the first N steps are applied.
+pub(crate) fn replay<V: Display>(
+    steps: Vec<Op<V>>,
+    stop_after: Option<usize>,
+    mut output: impl std::io::Write,
+) -> std::io::Result<Dictionary<V>> {
+    let mut dictionary = Dictionary::new();
+    let count = stop_after.unwrap_or(steps.len());
+    for step in steps.into_iter().take(count) {
+        let (label, action) = match &step {
+            Op::Insert(label, _) => (label, "replaces"),
+            Op::Remove(label) => (label, "removes"),
+        };
+        match dictionary.get(label) {
+            Some(value) => writeln!(
+                output,
+                "After \"{}\" ({} {} {}):",
+                step, action, label, value
+            )?,
+            None => writeln!(output, "After \"{}\":", step)?,
+        }
+        dictionary.handle(step);
+        writeln!(output, "{}", format_boxes(&dictionary))?;
+    }
+    Ok(dictionary)
+}
+
+// Every non-empty box as `{"box": n, "lenses": [{"label": ..., "focal_length": ...}]}`,
+// one box per line so two snapshots diff cleanly.
+pub(crate) fn json_snapshot(dictionary: &Dictionary<usize>) -> String {
+    let boxes = dictionary
+        .iter_boxes()
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(index, entries)| {
+            let lenses = entries
+                .iter()
+                .map(|(label, focal_length)| {
+                    format!(
+                        "{{\"label\": {}, \"focal_length\": {}}}",
+                        json_string(label),
+                        focal_length
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!("  {{\"box\": {}, \"lenses\": [{}]}}", index, lenses)
+        })
+        .collect::<Vec<String>>();
+    if boxes.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", boxes.join(",\n"))
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => write!(quoted, "\\u{:04x}", c as u32).unwrap(),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hashmap::parse_steps;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
+
+    #[test]
+    fn test_replay_stops_after_step() {
+        let mut output = vec![];
+        let dictionary = replay(parse_steps(EXAMPLE).unwrap(), Some(4), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "After \"rn=1\":
+Box 0: [rn 1]
+
+After \"cm-\":
+Box 0: [rn 1]
+
+After \"qp=3\":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After \"cm=2\":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+
+"
+        );
+        assert_eq!(
+            dictionary.focusing_power(|focal_length| *focal_length),
+            1 + 4 + 6
+        );
+    }
+
+    #[test]
+    fn test_replay_notes_replaced_lenses() {
+        let mut output = vec![];
+        replay(parse_steps(EXAMPLE).unwrap(), None, &mut output).unwrap();
+        let headers = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .filter(|line| line.contains('('))
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            headers,
+            vec![
+                "After \"qp-\" (removes qp 3):".to_string(),
+                "After \"pc-\" (removes pc 4):".to_string(),
+                "After \"ot=7\" (replaces ot 9):".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_snapshot() {
+        let dictionary = replay(parse_steps(EXAMPLE).unwrap(), None, std::io::sink()).unwrap();
+        assert_eq!(
+            format_boxes(&dictionary),
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]\n"
+        );
+        assert_eq!(
+            json_snapshot(&dictionary),
+            r#"[
+  {"box": 0, "lenses": [{"label": "rn", "focal_length": 1}, {"label": "cm", "focal_length": 2}]},
+  {"box": 3, "lenses": [{"label": "ot", "focal_length": 7}, {"label": "ab", "focal_length": 5}, {"label": "pc", "focal_length": 6}]}
+]
+"#
+        );
+        assert_eq!(json_snapshot(&Dictionary::new()), "[]\n");
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+    }
+}
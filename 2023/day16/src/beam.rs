@@ -0,0 +1,366 @@
+use crate::grammar;
+use common::parse::parse;
+use rayon::prelude::*;
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    fn from_offset(offset: (isize, isize)) -> Direction {
+        match offset {
+            (0, -1) => Direction::Up,
+            (0, 1) => Direction::Down,
+            (-1, 0) => Direction::Left,
+            (1, 0) => Direction::Right,
+            _ => panic!("Invalid offset {:?}", offset),
+        }
+    }
+
+    fn is_horizontal(&self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Entry {
+    pub(crate) position: (usize, usize),
+    pub(crate) direction: Direction,
+}
+
+impl Entry {
+    pub(crate) fn new(position: (usize, usize), direction: Direction) -> Entry {
+        Entry {
+            position,
+            direction,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TileSet {
+    words: Vec<u64>,
+}
+
+impl TileSet {
+    fn new(size: usize) -> TileSet {
+        TileSet {
+            words: vec![0; size.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    fn union_with(&mut self, other: &TileSet) {
+        self.words
+            .iter_mut()
+            .zip(other.words.iter())
+            .for_each(|(word, other)| *word |= other);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
+
+// The only places where a beam branches are splitters hit side-on, and whatever a
+// splitter lights up does not depend on where the beam came from. Each such splitter
+// is a node of a graph whose edges are the straight (or mirrored) runs between them,
+// so the energised tiles behind every node are computed once and shared by all entries.
+pub(crate) struct BeamEngine {
+    layout: Vec<Vec<char>>,
+    width: usize,
+    height: usize,
+    nodes: HashMap<(usize, usize), usize>,
+    energised: Vec<TileSet>,
+}
+
+impl BeamEngine {
+    // Panics with the position of the problem on malformed input.
+    pub(crate) fn new(input: &str) -> BeamEngine {
+        let layout =
+            parse(grammar::contraption(), input).unwrap_or_else(|error| panic!("{}", error));
+        let height = layout.len();
+        let width = layout[0].len();
+        let nodes = layout
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, tile)| **tile == '-' || **tile == '|')
+                    .map(move |(x, _)| (x, y))
+            })
+            .enumerate()
+            .map(|(index, position)| (position, index))
+            .collect::<HashMap<(usize, usize), usize>>();
+        let mut engine = BeamEngine {
+            layout,
+            width,
+            height,
+            nodes,
+            energised: vec![],
+        };
+        engine.energised = engine.energise_nodes();
+        engine
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    pub(crate) fn tile(&self, position: (usize, usize)) -> char {
+        self.layout[position.1][position.0]
+    }
+
+    pub(crate) fn index(&self, position: (usize, usize)) -> usize {
+        position.1 * self.width + position.0
+    }
+
+    fn step(&self, position: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.offset();
+        let x = position.0.checked_add_signed(dx)?;
+        let y = position.1.checked_add_signed(dy)?;
+        if x < self.width && y < self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    // Directions a beam leaves a tile in after entering it heading `direction`.
+    fn bounce(&self, position: (usize, usize), direction: Direction) -> Vec<Direction> {
+        let (dx, dy) = direction.offset();
+        match self.tile(position) {
+            '\\' => vec![Direction::from_offset((dy, dx))],
+            '/' => vec![Direction::from_offset((-dy, -dx))],
+            '-' if !direction.is_horizontal() => vec![Direction::Left, Direction::Right],
+            '|' if direction.is_horizontal() => vec![Direction::Up, Direction::Down],
+            _ => vec![direction],
+        }
+    }
+
+    // Follows a single beam until it leaves the grid or reaches a splitter side-on.
+    // Mirrors alone can send a beam around in circles, so the run is capped at the
+    // number of (tile, direction) states.
+    fn trace(&self, entry: Entry) -> (TileSet, Option<usize>) {
+        let mut tiles = TileSet::new(self.width * self.height);
+        let mut position = entry.position;
+        let mut direction = entry.direction;
+        for _ in 0..4 * self.width * self.height {
+            tiles.insert(self.index(position));
+            let directions = self.bounce(position, direction);
+            if directions.len() > 1 {
+                return (tiles, Some(self.nodes[&position]));
+            }
+            direction = directions[0];
+            position = match self.step(position, direction) {
+                Some(next) => next,
+                None => return (tiles, None),
+            };
+        }
+        (tiles, None)
+    }
+
+    fn energise_nodes(&self) -> Vec<TileSet> {
+        let mut own = vec![TileSet::new(self.width * self.height); self.nodes.len()];
+        let mut successors = vec![vec![]; self.nodes.len()];
+        for (position, node) in &self.nodes {
+            let split = if self.tile(*position) == '-' {
+                [Direction::Left, Direction::Right]
+            } else {
+                [Direction::Up, Direction::Down]
+            };
+            own[*node].insert(self.index(*position));
+            for direction in split {
+                if let Some(next) = self.step(*position, direction) {
+                    let (tiles, end) = self.trace(Entry::new(next, direction));
+                    own[*node].union_with(&tiles);
+                    successors[*node].extend(end);
+                }
+            }
+        }
+
+        (0..self.nodes.len())
+            .into_par_iter()
+            .map(|node| {
+                let mut reached = vec![false; self.nodes.len()];
+                let mut energised = TileSet::new(self.width * self.height);
+                let mut queue = VecDeque::from([node]);
+                reached[node] = true;
+                while let Some(current) = queue.pop_front() {
+                    energised.union_with(&own[current]);
+                    for next in &successors[current] {
+                        if !reached[*next] {
+                            reached[*next] = true;
+                            queue.push_back(*next);
+                        }
+                    }
+                }
+                energised
+            })
+            .collect()
+    }
+
+    pub(crate) fn energised_tiles(&self, entry: Entry) -> TileSet {
+        let (mut tiles, end) = self.trace(entry);
+        if let Some(node) = end {
+            tiles.union_with(&self.energised[node]);
+        }
+        tiles
+    }
+
+    pub(crate) fn energised_count(&self, entry: Entry) -> usize {
+        self.energised_tiles(entry).len()
+    }
+
+    // Every (tile, heading) state the beam reaches, grouped by the step it got there.
+    pub(crate) fn waves(&self, entry: Entry) -> Vec<Vec<Entry>> {
+        let mut seen = vec![[false; 4]; self.width * self.height];
+        seen[self.index(entry.position)][entry.direction as usize] = true;
+        let mut waves = vec![vec![entry]];
+        loop {
+            let mut next_wave = vec![];
+            for beam in waves.last().unwrap() {
+                for direction in self.bounce(beam.position, beam.direction) {
+                    let Some(next) = self.step(beam.position, direction) else {
+                        continue;
+                    };
+                    let state = &mut seen[self.index(next)][direction as usize];
+                    if !*state {
+                        *state = true;
+                        next_wave.push(Entry::new(next, direction));
+                    }
+                }
+            }
+            if next_wave.is_empty() {
+                return waves;
+            }
+            waves.push(next_wave);
+        }
+    }
+
+    pub(crate) fn edge_entries(&self) -> Vec<Entry> {
+        let (last_x, last_y) = (self.width - 1, self.height - 1);
+        let horizontal = (0..self.height).flat_map(|y| {
+            [
+                Entry::new((0, y), Direction::Right),
+                Entry::new((last_x, y), Direction::Left),
+            ]
+        });
+        let vertical = (0..self.width).flat_map(|x| {
+            [
+                Entry::new((x, 0), Direction::Down),
+                Entry::new((x, last_y), Direction::Up),
+            ]
+        });
+        horizontal.chain(vertical).collect()
+    }
+
+    pub(crate) fn best_entry(&self) -> (Entry, usize) {
+        self.edge_entries()
+            .into_par_iter()
+            .map(|entry| (entry, self.energised_count(entry)))
+            .max_by_key(|(entry, count)| (*count, std::cmp::Reverse(self.index(entry.position))))
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn test_energised_count() {
+        let engine = BeamEngine::new(EXAMPLE);
+        assert_eq!(
+            engine.energised_count(Entry::new((0, 0), Direction::Right)),
+            46
+        );
+        assert_eq!(
+            engine.energised_count(Entry::new((3, 0), Direction::Down)),
+            51
+        );
+    }
+
+    #[test]
+    fn test_best_entry() {
+        let engine = BeamEngine::new(EXAMPLE);
+        assert_eq!(engine.edge_entries().len(), 40);
+        assert_eq!(
+            engine.best_entry(),
+            (Entry::new((3, 0), Direction::Down), 51)
+        );
+    }
+
+    #[test]
+    fn test_waves() {
+        let engine = BeamEngine::new(EXAMPLE);
+        let entry = Entry::new((0, 0), Direction::Right);
+        let waves = engine.waves(entry);
+        assert_eq!(waves[0], vec![entry]);
+        assert_eq!(waves[1], vec![Entry::new((1, 0), Direction::Right)]);
+        assert_eq!(waves[2], vec![Entry::new((1, 1), Direction::Down)]);
+        let mut energised = waves
+            .iter()
+            .flatten()
+            .map(|beam| beam.position)
+            .collect::<Vec<(usize, usize)>>();
+        energised.sort();
+        energised.dedup();
+        assert_eq!(energised.len(), engine.energised_count(entry));
+    }
+
+    #[test]
+    fn test_mirror_loop_terminates() {
+        let engine = BeamEngine::new(
+            r"/.\
+...
+\./",
+        );
+        assert_eq!(
+            engine.energised_count(Entry::new((1, 0), Direction::Right)),
+            8
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1, column 3: expected ., /, \\, | or -")]
+    fn test_malformed_layout() {
+        BeamEngine::new(".|x\n...\n");
+    }
+}
@@ -0,0 +1,222 @@
+use crate::beam::{BeamEngine, Direction, Entry};
+use std::borrow::Cow;
+use std::io::Write;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Style {
+    Plain,
+    Ansi,
+}
+
+const BACKGROUND: u8 = 0;
+const DEVICE: u8 = 1;
+const ENERGISED: u8 = 2;
+const ENERGISED_DEVICE: u8 = 3;
+const BEAM_FRONT: u8 = 4;
+const PALETTE: [u8; 15] = [
+    20, 20, 30, // background
+    110, 110, 130, // mirror or splitter
+    150, 80, 20, // energised tile
+    255, 200, 80, // energised mirror or splitter
+    255, 255, 255, // tiles the beam reached in this frame
+];
+
+fn bit(direction: Direction) -> u8 {
+    1 << direction as u8
+}
+
+// For every tile, the headings of all beams that entered it as a bit mask.
+fn direction_masks(engine: &BeamEngine, entry: Entry) -> Vec<u8> {
+    let mut masks = vec![0; engine.width() * engine.height()];
+    for beam in engine.waves(entry).iter().flatten() {
+        masks[engine.index(beam.position)] |= bit(beam.direction);
+    }
+    masks
+}
+
+fn arrow(mask: u8) -> char {
+    let left_right = bit(Direction::Left) | bit(Direction::Right);
+    let up_down = bit(Direction::Up) | bit(Direction::Down);
+    match mask {
+        m if m == bit(Direction::Up) => '↑',
+        m if m == bit(Direction::Down) => '↓',
+        m if m == bit(Direction::Left) => '←',
+        m if m == bit(Direction::Right) => '→',
+        m if m == left_right => '↔',
+        m if m == up_down => '↕',
+        m => char::from_digit(m.count_ones(), 10).unwrap(),
+    }
+}
+
+// The contraption with every energised empty tile replaced by the direction(s) the
+// beam crossed it in. With `Style::Ansi` energised tiles are also coloured.
+pub(crate) fn render_heatmap(engine: &BeamEngine, entry: Entry, style: Style) -> String {
+    let masks = direction_masks(engine, entry);
+    let mut lines = vec![];
+    for y in 0..engine.height() {
+        let mut line = String::new();
+        for x in 0..engine.width() {
+            let tile = engine.tile((x, y));
+            let mask = masks[engine.index((x, y))];
+            let (c, colour) = match (mask, tile) {
+                (0, tile) => (tile, "2"),
+                (_, '.') => (arrow(mask), "33"),
+                (_, tile) => (tile, "1;33"),
+            };
+            match style {
+                Style::Plain => line.push(c),
+                Style::Ansi => line.push_str(&format!("\x1b[{}m{}\x1b[0m", colour, c)),
+            }
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+fn draw_tile(
+    pixels: &mut [u8],
+    image_width: usize,
+    position: (usize, usize),
+    tile: char,
+    fill: u8,
+    ink: u8,
+    scale: usize,
+) {
+    for j in 0..scale {
+        for i in 0..scale {
+            let on_glyph = match tile {
+                '|' => i == scale / 2,
+                '-' => j == scale / 2,
+                '/' => i + j == scale - 1,
+                '\\' => i == j,
+                _ => false,
+            };
+            let pixel = (position.1 * scale + j) * image_width + position.0 * scale + i;
+            pixels[pixel] = if on_glyph { ink } else { fill };
+        }
+    }
+}
+
+// Writes an animated GIF of the beam spreading through the contraption, one frame
+// per `steps_per_frame` beam steps, with tiles drawn as `scale` pixel squares.
+pub(crate) fn write_animation<W: Write>(
+    engine: &BeamEngine,
+    entry: Entry,
+    output: W,
+    scale: usize,
+    steps_per_frame: usize,
+) -> Result<(), gif::EncodingError> {
+    let image_width = engine.width() * scale;
+    let image_height = engine.height() * scale;
+    let mut encoder = gif::Encoder::new(output, image_width as u16, image_height as u16, &PALETTE)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    let mut pixels = vec![BACKGROUND; image_width * image_height];
+    for y in 0..engine.height() {
+        for x in 0..engine.width() {
+            draw_tile(
+                &mut pixels,
+                image_width,
+                (x, y),
+                engine.tile((x, y)),
+                BACKGROUND,
+                DEVICE,
+                scale,
+            );
+        }
+    }
+
+    let waves = engine.waves(entry);
+    for frame_waves in waves.chunks(steps_per_frame.max(1)) {
+        let mut frame_pixels = pixels.clone();
+        for beam in frame_waves.iter().flatten() {
+            let tile = engine.tile(beam.position);
+            draw_tile(
+                &mut pixels,
+                image_width,
+                beam.position,
+                tile,
+                ENERGISED,
+                ENERGISED_DEVICE,
+                scale,
+            );
+            draw_tile(
+                &mut frame_pixels,
+                image_width,
+                beam.position,
+                tile,
+                BEAM_FRONT,
+                ENERGISED_DEVICE,
+                scale,
+            );
+        }
+        let mut frame = gif::Frame::from_indexed_pixels(
+            image_width as u16,
+            image_height as u16,
+            frame_pixels,
+            None,
+        );
+        frame.delay = 4;
+        encoder.write_frame(&frame)?;
+    }
+
+    let mut frame = gif::Frame {
+        width: image_width as u16,
+        height: image_height as u16,
+        buffer: Cow::Borrowed(&pixels),
+        delay: 300,
+        ..gif::Frame::default()
+    };
+    frame.dispose = gif::DisposalMethod::Keep;
+    encoder.write_frame(&frame)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....";
+
+    #[test]
+    fn test_render_heatmap() {
+        let engine = BeamEngine::new(EXAMPLE);
+        let heatmap = render_heatmap(&engine, Entry::new((0, 0), Direction::Right), Style::Plain);
+        let expected = r"→|←←←\....
+|↓-.\↑....
+.↓...|-→→→
+.↓...↓↑.|.
+.↓...↓↑...
+.↓...↓↑..\
+.↓../2\\..
+←-→-/↓↓|..
+.|←←←2-|.\
+.↓//.|.↓..";
+        assert_eq!(heatmap, expected);
+    }
+
+    #[test]
+    fn test_write_animation() {
+        let engine = BeamEngine::new(EXAMPLE);
+        let mut gif = vec![];
+        write_animation(
+            &engine,
+            Entry::new((0, 0), Direction::Right),
+            &mut gif,
+            3,
+            5,
+        )
+        .unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(u16::from_le_bytes([gif[6], gif[7]]), 30);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+}
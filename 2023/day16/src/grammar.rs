@@ -0,0 +1,6 @@
+use common::parse::{grid, Parser};
+
+// Empty space `.`, mirrors `/` and `\` and splitters `|` and `-`.
+pub(crate) fn contraption<'a>() -> impl Parser<'a, Vec<Vec<char>>> {
+    grid("., /, \\, | or -", |c| "./\\|-".contains(c).then_some(c))
+}
@@ -1,7 +1,40 @@
+mod beam;
+mod export;
+mod grammar;
 mod part1;
 mod part2;
 
+use beam::{BeamEngine, Direction, Entry};
+use common::cli::usage_error;
+
 fn main() {
     part1::run();
     part2::run();
+
+    // `cargo run -- --heatmap` (or `--heatmap-plain`) prints the part 1 beam,
+    // `--animate beam.gif` records it
+    let args = std::env::args().collect::<Vec<String>>();
+    let entry = Entry::new((0, 0), Direction::Right);
+    let style = if args.iter().any(|arg| arg == "--heatmap") {
+        Some(export::Style::Ansi)
+    } else if args.iter().any(|arg| arg == "--heatmap-plain") {
+        Some(export::Style::Plain)
+    } else {
+        None
+    };
+    if let Some(style) = style {
+        let input = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+        let engine = BeamEngine::new(&input);
+        println!("{}", export::render_heatmap(&engine, entry, style));
+    }
+    if let Some(position) = args.iter().position(|arg| arg == "--animate") {
+        let output_path = args
+            .get(position + 1)
+            .unwrap_or_else(|| usage_error("cargo run -- --animate <output.gif>"));
+        let input = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+        let engine = BeamEngine::new(&input);
+        let file = std::fs::File::create(output_path).unwrap();
+        export::write_animation(&engine, entry, std::io::BufWriter::new(file), 4, 10).unwrap();
+        println!("Beam animation written to {}", output_path);
+    }
 }
@@ -0,0 +1,6 @@
+use common::parse::{grid, Parser};
+
+// The heat loss of every city block, one digit each.
+pub(crate) fn city<'a>() -> impl Parser<'a, Vec<Vec<usize>>> {
+    grid("digit", |c| c.to_digit(10).map(|digit| digit as usize))
+}
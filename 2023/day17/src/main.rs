@@ -1,3 +1,4 @@
+mod grammar;
 mod part1;
 mod part2;
 mod graph_tools;
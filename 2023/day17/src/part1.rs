@@ -1,4 +1,6 @@
+use crate::grammar;
 use crate::graph_tools::dijkstra;
+use common::parse::parse;
 
 pub fn run() {
     let input_path = "src/inputs/input.txt";
@@ -8,14 +10,7 @@ pub fn run() {
 }
 
 fn evaluate(input: &str) -> usize {
-    let grid = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect::<Vec<usize>>()
-        })
-        .collect::<Vec<Vec<usize>>>();
+    let grid = parse(grammar::city(), input).unwrap_or_else(|error| panic!("{}", error));
 
     let start = dijkstra::Point { x: 0, y: 0 };
     let end = dijkstra::Point {
@@ -67,14 +62,7 @@ mod tests {
 1224686865563
 2546548887735
 4322674655533";
-        let grid = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).unwrap() as usize)
-                    .collect::<Vec<usize>>()
-            })
-            .collect::<Vec<Vec<usize>>>();
+        let grid = parse(grammar::city(), input).unwrap();
         let start = dijkstra::Point { x: 0, y: 0 };
         let end = dijkstra::Point { x: 12, y: 12 };
         let cost = dijkstra::dijkstra(&grid, &start, |node| node.point == end, neighbors);
@@ -0,0 +1,219 @@
+use crate::grammar;
+use common::geometry::pick_interior_points;
+use common::parse::parse;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Encoding {
+    // `R 6 (#70c710)`, direction and distance in the first two columns
+    Plain,
+    // `R 6 (#70c710)`, distance in the first five hex digits, direction in the last one
+    Hex,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn offset(&self) -> (i128, i128) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Instruction {
+    pub(crate) direction: Direction,
+    pub(crate) distance: i128,
+}
+
+// Follows the dig plan one instruction at a time, keeping only the current
+// position and the running shoelace sum instead of the whole vertex list.
+#[derive(Debug, Clone)]
+pub(crate) struct Trench {
+    position: (i128, i128),
+    double_area: i128,
+    perimeter: i128,
+    min: (i128, i128),
+    max: (i128, i128),
+}
+
+impl Trench {
+    pub(crate) fn new() -> Trench {
+        Trench {
+            position: (0, 0),
+            double_area: 0,
+            perimeter: 0,
+            min: (0, 0),
+            max: (0, 0),
+        }
+    }
+
+    pub(crate) fn dig(&mut self, instruction: &Instruction) {
+        let (dx, dy) = instruction.direction.offset();
+        let (x, y) = self.position;
+        let next = (x + dx * instruction.distance, y + dy * instruction.distance);
+        self.double_area += x * next.1 - y * next.0;
+        self.perimeter += instruction.distance;
+        self.position = next;
+        self.min = (self.min.0.min(next.0), self.min.1.min(next.1));
+        self.max = (self.max.0.max(next.0), self.max.1.max(next.1));
+    }
+
+    pub(crate) fn position(&self) -> (i128, i128) {
+        self.position
+    }
+
+    pub(crate) fn area(&self) -> i128 {
+        self.double_area.abs() / 2
+    }
+
+    pub(crate) fn perimeter(&self) -> i128 {
+        self.perimeter
+    }
+
+    // Cubic metres of lava: the trench itself plus everything it encloses.
+    pub(crate) fn lagoon_size(&self) -> i128 {
+        pick_interior_points(self.double_area.abs(), self.perimeter) + self.perimeter
+    }
+
+    pub(crate) fn bounds(&self) -> ((i128, i128), (i128, i128)) {
+        (self.min, self.max)
+    }
+}
+
+// Reads the plan lazily, one line at a time. Panics with the position of the problem
+// on malformed input.
+pub(crate) fn instructions(
+    input: &str,
+    encoding: Encoding,
+) -> impl Iterator<Item = Instruction> + '_ {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(move |(index, line)| {
+            parse(grammar::instruction(encoding), line).unwrap_or_else(|mut error| {
+                error.line = index + 1;
+                panic!("{}", error)
+            })
+        })
+}
+
+pub(crate) fn dig_all(input: &str, encoding: Encoding) -> Trench {
+    instructions(input, encoding).fold(Trench::new(), |mut trench, instruction| {
+        trench.dig(&instruction);
+        trench
+    })
+}
+
+// Draws the trench outline scaled down so that its longest side is `size` pixels.
+// The plan is read twice: once for the bounds and once to emit the path.
+pub(crate) fn render_svg(input: &str, encoding: Encoding, size: f64) -> String {
+    let ((min_x, min_y), (max_x, max_y)) = dig_all(input, encoding).bounds();
+    let extent = (max_x - min_x).max(max_y - min_y).max(1) as f64;
+    let scale = size / extent;
+    let to_svg = |(x, y): (i128, i128)| ((x - min_x) as f64 * scale, (y - min_y) as f64 * scale);
+
+    let mut trench = Trench::new();
+    let (start_x, start_y) = to_svg(trench.position());
+    let mut path = format!("M {:.2} {:.2}", start_x, start_y);
+    for instruction in instructions(input, encoding) {
+        trench.dig(&instruction);
+        let (x, y) = to_svg(trench.position());
+        path.push_str(&format!(" L {:.2} {:.2}", x, y));
+    }
+    path.push_str(" Z");
+
+    let width = (max_x - min_x) as f64 * scale;
+    let height = (max_y - min_y) as f64 * scale;
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -1 {:.2} {:.2}\" width=\"{:.0}\" height=\"{:.0}\">\n  \
+         <path d=\"{}\" fill=\"#cfe8ff\" stroke=\"#1f4e79\" stroke-width=\"1\" />\n</svg>\n",
+        width + 2.0,
+        height + 2.0,
+        width + 2.0,
+        height + 2.0,
+        path
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::ParseError;
+
+    const EXAMPLE: &str = "R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)";
+
+    fn read(line: &str, encoding: Encoding) -> Result<Instruction, ParseError> {
+        parse(grammar::instruction(encoding), line)
+    }
+
+    #[test]
+    fn test_instruction() {
+        let plain = read("R 6 (#70c710)", Encoding::Plain).unwrap();
+        assert_eq!(plain.direction, Direction::Right);
+        assert_eq!(plain.distance, 6);
+        let hex = read("R 6 (#70c710)", Encoding::Hex).unwrap();
+        assert_eq!(hex.direction, Direction::Right);
+        assert_eq!(hex.distance, 461937);
+        let hex_only = read("#0dc571", Encoding::Hex).unwrap();
+        assert_eq!(hex_only.direction, Direction::Down);
+        assert_eq!(hex_only.distance, 56407);
+        let error = read("#0dc571", Encoding::Plain).unwrap_err();
+        assert_eq!(error.expected, vec!["direction and distance".to_string()]);
+        let error = read("R 6 (#70c714)", Encoding::Hex).unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (7, "\"70c714\""));
+    }
+
+    #[test]
+    fn test_dig_all() {
+        let trench = dig_all(EXAMPLE, Encoding::Plain);
+        assert_eq!(trench.position(), (0, 0));
+        assert_eq!(trench.perimeter(), 38);
+        assert_eq!(trench.area(), 42);
+        assert_eq!(trench.lagoon_size(), 62);
+        assert_eq!(trench.bounds(), ((0, 0), (6, 9)));
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2, column 1")]
+    fn test_instructions_error_line() {
+        instructions("R 6 (#70c710)\nX 1 (#70c710)", Encoding::Plain).count();
+    }
+
+    #[test]
+    fn test_dig_all_hex() {
+        let trench = dig_all(EXAMPLE, Encoding::Hex);
+        assert_eq!(trench.lagoon_size(), 952408144115);
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let svg = render_svg(EXAMPLE, Encoding::Plain, 90.0);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("d=\"M 0.00 0.00 L 60.00 0.00 L 60.00 50.00"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
@@ -0,0 +1,57 @@
+use crate::dig_plan::{Direction, Encoding, Instruction};
+use common::parse::{cell, integer, spaces1, tag, take_while1, Parser};
+
+// `R 6`
+fn plain<'a>() -> impl Parser<'a, Instruction> {
+    let direction = cell("U, D, L or R", |c| match c {
+        'U' => Some(Direction::Up),
+        'D' => Some(Direction::Down),
+        'L' => Some(Direction::Left),
+        'R' => Some(Direction::Right),
+        _ => None,
+    });
+    direction
+        .then_ignore(spaces1())
+        .then(integer())
+        .map(|(direction, distance)| Instruction {
+            direction,
+            distance,
+        })
+}
+
+// `(#70c710)`: five hex digits of distance, then 0 to 3 for right, down, left and up.
+fn hex<'a>() -> impl Parser<'a, Instruction> {
+    let colour = take_while1("hex digit", |c| c.is_ascii_hexdigit()).try_map(|colour| {
+        let direction = match colour.as_bytes() {
+            [_, _, _, _, _, b'0'] => Direction::Right,
+            [_, _, _, _, _, b'1'] => Direction::Down,
+            [_, _, _, _, _, b'2'] => Direction::Left,
+            [_, _, _, _, _, b'3'] => Direction::Up,
+            _ => return Err("six hex digits ending in 0, 1, 2 or 3".to_string()),
+        };
+        let distance = i128::from_str_radix(&colour[..5], 16).unwrap();
+        Ok(Instruction {
+            direction,
+            distance,
+        })
+    });
+    tag("(")
+        .optional()
+        .ignore_then(tag("#"))
+        .ignore_then(colour)
+        .then_ignore(tag(")").optional())
+}
+
+// `R 6 (#70c710)`, read with either encoding. The hex one does without the plain
+// half.
+pub(crate) fn instruction<'a>(encoding: Encoding) -> impl Parser<'a, Instruction> {
+    plain()
+        .then_ignore(spaces1())
+        .optional()
+        .then(hex())
+        .try_map(move |(plain, hex)| match (encoding, plain) {
+            (Encoding::Plain, Some(plain)) => Ok(plain),
+            (Encoding::Plain, None) => Err("direction and distance".to_string()),
+            (Encoding::Hex, _) => Ok(hex),
+        })
+}
@@ -1,7 +1,32 @@
+mod dig_plan;
+mod grammar;
 mod part1;
 mod part2;
 
+use common::cli::usage_error;
+use dig_plan::Encoding;
+
 fn main() {
     part1::run();
     part2::run();
+
+    // `cargo run -- --svg trench.svg` or `--svg-hex` for the part 2 plan
+    let args = std::env::args().collect::<Vec<String>>();
+    for (flag, encoding) in [("--svg", Encoding::Plain), ("--svg-hex", Encoding::Hex)] {
+        if let Some(position) = args.iter().position(|arg| arg == flag) {
+            let output_path = args
+                .get(position + 1)
+                .unwrap_or_else(|| usage_error(&format!("cargo run -- {} <output.svg>", flag)));
+            let input = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+            let svg = dig_plan::render_svg(&input, encoding, 800.0);
+            std::fs::write(output_path, svg).unwrap();
+            let trench = dig_plan::dig_all(&input, encoding);
+            println!(
+                "Trench outline written to {} (area {}, perimeter {})",
+                output_path,
+                trench.area(),
+                trench.perimeter()
+            );
+        }
+    }
 }
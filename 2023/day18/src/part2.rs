@@ -1,3 +1,5 @@
+use crate::dig_plan::{dig_all, Encoding};
+
 pub(crate) fn run() {
     let input_path = "src/inputs/input.txt";
     let input = std::fs::read_to_string(input_path).unwrap();
@@ -5,100 +7,14 @@ pub(crate) fn run() {
     println!("Part 2: {}", result);
 }
 
-fn evaluate(input: &str) -> isize {
-    let start = Point::new(0, 0);
-    let mut points = vec![start];
-    let instructions = input
-        .lines()
-        .map(|l| Instruction::new(l))
-        .collect::<Vec<Instruction>>();
-    instructions.iter().for_each(|instruction| {
-        let last = points.iter().last().unwrap();
-        let next = move_to(&last, &instruction.direction, instruction.distance);
-        points.push(next);
-    });
-    points.pop();
-    let area = shoelace(&points);
-    let premiter = instructions.iter().map(|i| i.distance).sum::<usize>() as isize;
-    (area + premiter) / 2 + 1
-}
-
-#[allow(dead_code)]
-fn grid_to_string(grid: &Vec<Vec<char>>) -> String {
-    grid.iter()
-        .map(|row| row.iter().collect::<String>())
-        .collect::<Vec<String>>()
-        .join("\n")
-}
-
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl Point {
-    fn new(x: isize, y: isize) -> Point {
-        Point { x, y }
-    }
-
-    fn cross_product(&self, other: &Point) -> isize {
-        self.x * other.y - self.y * other.x
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-struct Instruction {
-    direction: Direction,
-    distance: usize,
-}
-
-impl Instruction {
-    fn new(input: &str) -> Instruction {
-        let instruction_parts = input.split(' ').collect::<Vec<&str>>();
-        let distance = usize::from_str_radix(&instruction_parts[2][2..7], 16).unwrap();
-        let direction = match &instruction_parts[2][7..8] {
-            "3" => Direction::Up,
-            "1" => Direction::Down,
-            "2" => Direction::Left,
-            "0" => Direction::Right,
-            _ => panic!("Invalid direction"),
-        };
-        Instruction {
-            direction,
-            distance,
-        }
-    }
-}
-
-fn move_to(start: &Point, direction: &Direction, distance: usize) -> Point {
-    match direction {
-        Direction::Up => Point::new(start.x, start.y - distance as isize),
-        Direction::Down => Point::new(start.x, start.y + distance as isize),
-        Direction::Left => Point::new(start.x - distance as isize, start.y),
-        Direction::Right => Point::new(start.x + distance as isize, start.y),
-    }
-}
-
-fn shoelace(points: &Vec<Point>) -> isize {
-    points
-        .iter()
-        .zip(points.iter().cycle().skip(1))
-        .map(|(p1, p2)| p1.cross_product(p2))
-        .sum::<isize>()
+fn evaluate(input: &str) -> i128 {
+    dig_all(input, Encoding::Hex).lagoon_size()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dig_plan::{instructions, Instruction};
 
     #[test]
     fn test_evaluate() {
@@ -137,23 +53,7 @@ R 2 (#7807d2)
 U 3 (#a77fa3)
 L 2 (#015232)
 U 2 (#7a21e3)";
-        let instructions = input
-            .lines()
-            .map(|l| Instruction::new(l))
-            .collect::<Vec<Instruction>>();
+        let instructions = instructions(input, Encoding::Hex).collect::<Vec<Instruction>>();
         assert_eq!(instructions[0].distance, 461937);
     }
-
-    #[test]
-    fn test_shoelace() {
-        let points = vec![
-            Point::new(1, 6),
-            Point::new(3, 1),
-            Point::new(7, 2),
-            Point::new(4, 4),
-            Point::new(8, 5),
-        ];
-        let area = shoelace(&points) as f64 / 2.0;
-        assert_eq!(area, 16.5);
-    }
 }
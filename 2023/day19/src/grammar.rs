@@ -0,0 +1,122 @@
+use crate::workflow::{Category, Condition, Destination, Part, Rule, Workflows};
+use common::parse::{cell, integer, lines, newline, tag, word, Parser};
+use std::cmp::Ordering;
+
+fn category<'a>() -> impl Parser<'a, Category> {
+    cell("x, m, a or s", |c| match c {
+        'x' => Some(Category::X),
+        'm' => Some(Category::M),
+        'a' => Some(Category::A),
+        's' => Some(Category::S),
+        _ => None,
+    })
+}
+
+fn destination<'a>() -> impl Parser<'a, Destination> {
+    word().map(|name| match name {
+        "A" => Destination::A,
+        "R" => Destination::R,
+        name => Destination::Workflow(name.to_string()),
+    })
+}
+
+// `a<2006:qkq` or `rfg`. A workflow may be called `a`, so a rule that is not a
+// condition is read again as a plain destination.
+fn rule<'a>() -> impl Parser<'a, Rule> {
+    let ordering = cell("< or >", |c| match c {
+        '<' => Some(Ordering::Less),
+        '>' => Some(Ordering::Greater),
+        _ => None,
+    });
+    let condition =
+        category()
+            .then(ordering)
+            .then(integer())
+            .map(|((category, ordering), value)| Condition {
+                category,
+                ordering,
+                value,
+            });
+    let conditional =
+        condition
+            .then_ignore(tag(":"))
+            .then(destination())
+            .map(|(condition, destination)| Rule {
+                condition: Some(condition),
+                destination,
+            });
+    conditional.or(destination().map(|destination| Rule {
+        condition: None,
+        destination,
+    }))
+}
+
+// `px{a<2006:qkq,m>2090:A,rfg}`
+fn workflow<'a>() -> impl Parser<'a, (String, Vec<Rule>)> {
+    word()
+        .map(str::to_string)
+        .then_ignore(tag("{"))
+        .then(rule().separated_by(tag(",")))
+        .then_ignore(tag("}"))
+}
+
+// `{x=787,m=2655,a=1222,s=2876}`
+fn part<'a>() -> impl Parser<'a, Part> {
+    let rating = category().then_ignore(tag("=")).then(integer());
+    tag("{")
+        .ignore_then(rating.separated_by(tag(",")))
+        .then_ignore(tag("}"))
+        .map(|ratings| {
+            let mut part = Part { ratings: [0; 4] };
+            for (category, value) in ratings {
+                part.ratings[category as usize] = value;
+            }
+            part
+        })
+}
+
+// The workflows, a blank line, then the parts.
+pub(crate) fn system<'a>() -> impl Parser<'a, (Workflows, Vec<Part>)> {
+    lines(workflow())
+        .map(|workflows| workflows.into_iter().collect())
+        .then_ignore(newline().repeated1())
+        .then(lines(part()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_system() {
+        let (workflows, parts) = parse(system(), "in{a<5:a,R}\na{A}\n\n{x=1,s=4}\n").unwrap();
+        assert_eq!(
+            workflows["in"],
+            vec![
+                Rule {
+                    condition: Some(Condition {
+                        category: Category::A,
+                        ordering: Ordering::Less,
+                        value: 5
+                    }),
+                    destination: Destination::Workflow("a".to_string())
+                },
+                Rule {
+                    condition: None,
+                    destination: Destination::R
+                }
+            ]
+        );
+        assert_eq!(workflows["a"].len(), 1);
+        assert_eq!(
+            parts,
+            vec![Part {
+                ratings: [1, 0, 0, 4]
+            }]
+        );
+        let error = parse(system(), "in{a<5:A,R}\n\n{x=1,y=2}").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+        assert_eq!(error.expected, vec!["\"}\"".to_string()]);
+    }
+}
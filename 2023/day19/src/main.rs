@@ -1,5 +1,7 @@
+mod grammar;
 mod part1;
 mod part2;
+mod workflow;
 
 fn main() {
     part1::run();
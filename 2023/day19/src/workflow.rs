@@ -0,0 +1,58 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Destination {
+    R,
+    A,
+    Workflow(String),
+}
+
+// `a<2006`: the part's rating in `category` compared to `value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Condition {
+    pub(crate) category: Category,
+    pub(crate) ordering: Ordering,
+    pub(crate) value: usize,
+}
+
+// `a<2006:qkq`, or just `rfg` for the rule that always applies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Rule {
+    pub(crate) condition: Option<Condition>,
+    pub(crate) destination: Destination,
+}
+
+impl Rule {
+    pub(crate) fn applies_to(&self, part: &Part) -> bool {
+        self.condition.as_ref().is_none_or(|condition| {
+            part.rating(condition.category).cmp(&condition.value) == condition.ordering
+        })
+    }
+}
+
+// `{x=787,m=2655,a=1222,s=2876}`; missing ratings are 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Part {
+    pub(crate) ratings: [usize; 4],
+}
+
+impl Part {
+    pub(crate) fn rating(&self, category: Category) -> usize {
+        self.ratings[category as usize]
+    }
+
+    pub(crate) fn sum(&self) -> usize {
+        self.ratings.iter().sum()
+    }
+}
+
+pub(crate) type Workflows = HashMap<String, Vec<Rule>>;
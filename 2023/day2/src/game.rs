@@ -0,0 +1,133 @@
+use crate::grammar;
+use common::parse::{parse, ParseError};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Colour {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Colour {
+    pub(crate) const ALL: [Colour; 3] = [Colour::Red, Colour::Green, Colour::Blue];
+}
+
+// A handful of cubes, counted per colour. Used both for what the elf pulls out of the
+// bag and for what the bag holds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Draw {
+    counts: [u32; 3],
+}
+
+impl Draw {
+    pub(crate) fn new(red: u32, green: u32, blue: u32) -> Draw {
+        Draw {
+            counts: [red, green, blue],
+        }
+    }
+
+    pub(crate) fn count(&self, colour: Colour) -> u32 {
+        self.counts[colour as usize]
+    }
+
+    // Colours that do not appear count as zero.
+    pub(crate) fn from_cubes(cubes: &[(u32, Colour)]) -> Draw {
+        let mut draw = Draw::default();
+        for (count, colour) in cubes {
+            draw.counts[*colour as usize] += count;
+        }
+        draw
+    }
+
+    // `3 blue, 4 red`
+    pub(crate) fn parse(text: &str) -> Result<Draw, ParseError> {
+        parse(grammar::draw(), text)
+    }
+
+    // Whether every colour of `self` fits in `bag`.
+    pub(crate) fn fits_in(&self, bag: &Draw) -> bool {
+        Colour::ALL
+            .iter()
+            .all(|colour| self.count(*colour) <= bag.count(*colour))
+    }
+
+    pub(crate) fn power(&self) -> u32 {
+        self.counts.iter().product()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Game {
+    pub(crate) id: u32,
+    pub(crate) draws: Vec<Draw>,
+}
+
+impl Game {
+    // Every draw could have come out of `bag`.
+    pub(crate) fn is_feasible(&self, bag: &Draw) -> bool {
+        self.draws.iter().all(|draw| draw.fits_in(bag))
+    }
+
+    // The fewest cubes of each colour the bag must have held.
+    pub(crate) fn minimum_bag(&self) -> Draw {
+        let mut bag = Draw::default();
+        for draw in &self.draws {
+            for colour in Colour::ALL {
+                let count = &mut bag.counts[colour as usize];
+                *count = (*count).max(draw.count(colour));
+            }
+        }
+        bag
+    }
+
+    pub(crate) fn power(&self) -> u32 {
+        self.minimum_bag().power()
+    }
+}
+
+pub(crate) fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse(grammar::games(), input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_game(line: &str) -> Result<Game, ParseError> {
+        parse(grammar::game(), line)
+    }
+
+    #[test]
+    fn test_parse() {
+        let game = parse_game("Game 12: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+        assert_eq!(game.id, 12);
+        assert_eq!(
+            game.draws,
+            vec![Draw::new(4, 0, 3), Draw::new(1, 2, 6), Draw::new(0, 2, 0)]
+        );
+        let error = parse_game("1: 3 blue").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected.clone()),
+            (1, vec!["\"Game \"".to_string()])
+        );
+        let error = parse_game("Game 1: 3 purple").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (11, "\"purple\""));
+        assert_eq!(error.expected, vec!["\"red\"", "\"green\"", "\"blue\""]);
+        let error = parse_game("Game 1: three blue").unwrap_err();
+        assert_eq!((error.column, error.expected[0].as_str()), (9, "number"));
+        let error = parse_games("Game 1: 3 blue\nGame 2: 3 blue 4 red").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+    }
+
+    #[test]
+    fn test_queries() {
+        let game =
+            parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red")
+                .unwrap();
+        assert!(!game.is_feasible(&Draw::new(12, 13, 14)));
+        assert!(game.is_feasible(&Draw::new(20, 13, 6)));
+        assert_eq!(game.minimum_bag(), Draw::new(20, 13, 6));
+        assert_eq!(game.power(), 1560);
+        assert_eq!(Draw::parse("14 blue, 12 red"), Ok(Draw::new(12, 0, 14)));
+    }
+}
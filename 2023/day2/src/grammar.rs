@@ -0,0 +1,30 @@
+use crate::game::{Colour, Draw, Game};
+use common::parse::{integer, lines, spaces1, tag, Parser};
+
+pub(crate) fn colour<'a>() -> impl Parser<'a, Colour> {
+    tag("red")
+        .to(Colour::Red)
+        .or(tag("green").to(Colour::Green))
+        .or(tag("blue").to(Colour::Blue))
+}
+
+// `3 blue, 4 red`
+pub(crate) fn draw<'a>() -> impl Parser<'a, Draw> {
+    let cubes = integer().then_ignore(spaces1()).then(colour());
+    cubes
+        .separated_by(tag(",").padded())
+        .map(|cubes| Draw::from_cubes(&cubes))
+}
+
+// `Game 1: 3 blue, 4 red; 1 red, 2 green`
+pub(crate) fn game<'a>() -> impl Parser<'a, Game> {
+    tag("Game ")
+        .ignore_then(integer())
+        .then_ignore(tag(":").padded())
+        .then(draw().separated_by(tag(";").padded()))
+        .map(|(id, draws)| Game { id, draws })
+}
+
+pub(crate) fn games<'a>() -> impl Parser<'a, Vec<Game>> {
+    lines(game())
+}
@@ -1,7 +1,24 @@
+mod game;
+mod grammar;
 mod part1;
 mod part2;
 
+use common::cli::usage_error;
+use game::Draw;
+
 fn main() {
-    part1::main();
+    // `cargo run -- --bag "12 red, 13 green, 14 blue"` checks the games against
+    // another bag
+    let args = std::env::args().collect::<Vec<String>>();
+    let bag = match args.iter().position(|arg| arg == "--bag") {
+        Some(position) => {
+            let bag = args
+                .get(position + 1)
+                .unwrap_or_else(|| usage_error("cargo run -- --bag \"12 red, 13 green, 14 blue\""));
+            Draw::parse(bag).unwrap_or_else(|error| panic!("{}", error))
+        }
+        None => Draw::new(12, 13, 14),
+    };
+    part1::main(&bag);
     part2::main();
 }
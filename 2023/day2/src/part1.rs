@@ -1,99 +1,50 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use crate::game::{parse_games, Draw};
+use common::parse::ParseError;
 
-struct Game {
-    id: u32,
-    line: String,
-}
-
-impl Game {
-    fn is_valid(&self) -> bool {
-        return self.color_counts("red").iter().max().unwrap() <= &12
-            && self.color_counts("green").iter().max().unwrap() <= &13
-            && self.color_counts("blue").iter().max().unwrap() <= &14;
-    }
-
-    fn color_counts(&self, color: &str) -> Vec<u32> {
-        let regex_pattern = format!(r"(\d+) {}", regex::escape(color));
-        let re = regex::Regex::new(&regex_pattern).unwrap();
-        let mut counts = Vec::new();
-        for caps in re.captures_iter(&self.line) {
-            let count = caps.get(1).unwrap().as_str().parse::<u32>().unwrap();
-            counts.push(count);
-        }
-        counts
+pub fn main(bag: &Draw) {
+    let path = "src/inputs/input.txt";
+    let input = std::fs::read_to_string(path).expect("file not found");
+    match valid_games_sum(&input, bag) {
+        Ok(sum) => println!("Sum of valid games: {}", sum),
+        Err(error) => println!("Sum of valid games: {}", error),
     }
 }
 
-pub fn main() {
-    let path = r"src\inputs\input.txt";
-    let lines = read_file_to_lines(path);
-    let games = lines
+fn valid_games_sum(input: &str, bag: &Draw) -> Result<u32, ParseError> {
+    Ok(parse_games(input)?
         .iter()
-        .map(|line| parse_line(line))
-        .collect::<Vec<Game>>();
-    let valid_games_sum: u32 = games
-        .iter()
-        .filter(|game| game.is_valid())
+        .filter(|game| game.is_feasible(bag))
         .map(|game| game.id)
-        .sum();
-    println!("Sum of valid games: {}", valid_games_sum);
-}
-
-fn read_file_to_lines(filename: &str) -> Vec<String> {
-    let mut lines = Vec::new();
-    let file = File::open(filename).expect("file not found");
-    let buf_reader = BufReader::new(file);
-    for line in buf_reader.lines() {
-        lines.push(line.expect("Could not parse line"));
-    }
-    lines
-}
-
-fn parse_line(line: &str) -> Game {
-    let re = regex::Regex::new(r"Game (\d+):").unwrap();
-    let caps = re.captures(line).unwrap();
-    let id = caps.get(1).unwrap().as_str().parse::<u32>().unwrap();
-    Game {
-        id,
-        line: line.to_string(),
-    }
+        .sum())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grammar;
+    use common::parse::parse;
 
-    #[test]
-    fn test_parse_line() {
-        let line = "Game 1: 1 red, 2 green, 3 blue";
-        let game = parse_line(line);
-        assert_eq!(game.id, 1);
-        assert_eq!(game.line, line);
-    }
-
-    #[test]
-    fn test_color_counts() {
-        let line = "Game 1: 1 red, 2 green, 3 blue";
-        let game = parse_line(line);
-        assert_eq!(game.color_counts("red"), vec![1]);
-        assert_eq!(game.color_counts("green"), vec![2]);
-        assert_eq!(game.color_counts("blue"), vec![3]);
-    }
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
 
     #[test]
     fn test_is_valid() {
-        let line = "Game 1: 1 red, 2 green, 3 blue";
-        let game = parse_line(line);
-        assert_eq!(game.is_valid(), true);
+        let bag = Draw::new(12, 13, 14);
+        let game = parse(grammar::game(), "Game 1: 1 red, 2 green, 3 blue").unwrap();
+        assert!(game.is_feasible(&bag));
+        let game = parse(grammar::game(), "Game 1: 15 red, 13 green, 14 blue").unwrap();
+        assert!(!game.is_feasible(&bag));
     }
 
     #[test]
-    fn test_is_not_valid() {
-        let line = "Game 1: 15 red, 13 green, 14 blue";
-        let game = parse_line(line);
-        assert_eq!(game.is_valid(), false);
+    fn test_valid_games_sum() {
+        assert_eq!(valid_games_sum(EXAMPLE, &Draw::new(12, 13, 14)), Ok(8));
+        assert_eq!(
+            valid_games_sum(EXAMPLE, &Draw::new(20, 13, 14)),
+            Ok(1 + 2 + 3 + 5)
+        );
     }
 }
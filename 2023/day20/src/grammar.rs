@@ -0,0 +1,50 @@
+use common::parse::{lines, tag, word, Parser};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Kind {
+    FlipFlop,
+    Conjunction,
+    Broadcaster,
+}
+
+// `%a -> inv, con`, `&inv -> b` or `broadcaster -> a, b, c`.
+fn module<'a>() -> impl Parser<'a, (Kind, &'a str, Vec<&'a str>)> {
+    let prefix = tag("%")
+        .to(Kind::FlipFlop)
+        .or(tag("&").to(Kind::Conjunction));
+    let named = prefix.then(word());
+    let broadcaster = tag("broadcaster").map(|name| (Kind::Broadcaster, name));
+    named
+        .or(broadcaster)
+        .then_ignore(tag(" -> "))
+        .then(word().separated_by(tag(", ")))
+        .map(|((kind, name), outputs)| (kind, name, outputs))
+}
+
+pub(crate) fn configuration<'a>() -> impl Parser<'a, Vec<(Kind, &'a str, Vec<&'a str>)>> {
+    lines(module())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_configuration() {
+        assert_eq!(
+            parse(
+                configuration(),
+                "broadcaster -> a, b\n%a -> b\n&b -> output\n"
+            ),
+            Ok(vec![
+                (Kind::Broadcaster, "broadcaster", vec!["a", "b"]),
+                (Kind::FlipFlop, "a", vec!["b"]),
+                (Kind::Conjunction, "b", vec!["output"]),
+            ])
+        );
+        let error = parse(configuration(), "broadcaster -> a\n$a -> b").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected.len(), 3);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::grammar::{self, Kind};
+use common::parse::parse;
 use std::collections::{HashMap, LinkedList};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -30,23 +32,16 @@ struct Module {
 }
 
 impl Module {
-    fn new(puzzle_line: &str) -> Self {
-        let (type_and_name, outputs) = puzzle_line.split_once(" -> ").unwrap();
-        let (mtype, mname) = type_and_name.split_at(1);
-        let module_type = match mtype {
-            "%" => ModuleType::FlipFlop(false),
-            "&" => ModuleType::Conjunction(HashMap::new()),
-            _ => ModuleType::Broadcaster,
+    fn new((kind, name, outputs): (Kind, &str, Vec<&str>)) -> Self {
+        let module_type = match kind {
+            Kind::FlipFlop => ModuleType::FlipFlop(false),
+            Kind::Conjunction => ModuleType::Conjunction(HashMap::new()),
+            Kind::Broadcaster => ModuleType::Broadcaster,
         };
-        let name = match module_type {
-            ModuleType::Broadcaster => "broadcaster".to_string(),
-            _ => mname.to_string(),
-        };
-        let outputs = outputs.split(", ").map(|s| s.to_string()).collect();
         Self {
-            name,
+            name: name.to_string(),
             modlule_type: module_type,
-            outputs,
+            outputs: outputs.into_iter().map(str::to_string).collect(),
         }
     }
 
@@ -103,7 +98,11 @@ pub fn run() {
 }
 
 fn evaluate(input: &str) -> usize {
-    let mut modules: Vec<Module> = input.lines().map(|l| Module::new(l)).collect();
+    let mut modules: Vec<Module> = parse(grammar::configuration(), input)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .into_iter()
+        .map(Module::new)
+        .collect();
     populate_conjunction_inputs(&mut modules);
     let initial_modules = modules.clone();
     let mut low_pulse_count = 0;
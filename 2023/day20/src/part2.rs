@@ -1,5 +1,7 @@
-use std::{collections::{HashMap, LinkedList}, isize, usize};
-use num::Integer;
+use crate::grammar::{self, Kind};
+use common::parse::parse;
+use std::{collections::{HashMap, LinkedList}, usize};
+use common::number_theory::lcm_all;
 
 #[derive(Debug, Clone, PartialEq)]
 enum ModuleType {
@@ -31,23 +33,16 @@ struct Module {
 }
 
 impl Module {
-    fn new(puzzle_line: &str) -> Self {
-        let (type_and_name, outputs) = puzzle_line.split_once(" -> ").unwrap();
-        let (mtype, mname) = type_and_name.split_at(1);
-        let module_type = match mtype {
-            "%" => ModuleType::FlipFlop(false),
-            "&" => ModuleType::Conjunction(HashMap::new()),
-            _ => ModuleType::Broadcaster,
+    fn new((kind, name, outputs): (Kind, &str, Vec<&str>)) -> Self {
+        let module_type = match kind {
+            Kind::FlipFlop => ModuleType::FlipFlop(false),
+            Kind::Conjunction => ModuleType::Conjunction(HashMap::new()),
+            Kind::Broadcaster => ModuleType::Broadcaster,
         };
-        let name = match module_type {
-            ModuleType::Broadcaster => "broadcaster".to_string(),
-            _ => mname.to_string(),
-        };
-        let outputs = outputs.split(", ").map(|s| s.to_string()).collect();
         Self {
-            name,
+            name: name.to_string(),
             module_type,
-            outputs,
+            outputs: outputs.into_iter().map(str::to_string).collect(),
         }
     }
 
@@ -95,7 +90,11 @@ pub fn run() {
 }
 
 fn evaluate(input: &str) -> usize {
-    let mut modules: Vec<Module> = input.lines().map(|l| Module::new(l)).collect();
+    let mut modules: Vec<Module> = parse(grammar::configuration(), input)
+        .unwrap_or_else(|error| panic!("{}", error))
+        .into_iter()
+        .map(Module::new)
+        .collect();
     populate_conjunction_inputs(&mut modules);
     let mut queue: LinkedList<(String, Pulse, String)> = LinkedList::new();
     let mut button_presses = 0;
@@ -134,7 +133,7 @@ fn evaluate(input: &str) -> usize {
         }
     }
 
-    the_four_modules.iter().fold(1 as isize, |acc, (_, v)| acc.lcm(&(*v as isize))) as usize
+    lcm_all(the_four_modules.values().map(|v| *v as u64)).expect("Button presses overflow u64") as usize
 }
 
 #[cfg(test)]
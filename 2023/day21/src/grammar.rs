@@ -0,0 +1,6 @@
+use common::parse::{grid, Parser};
+
+// Garden plots `.`, rocks `#` and the starting position `S`.
+pub(crate) fn garden<'a>() -> impl Parser<'a, Vec<Vec<char>>> {
+    grid("., # or S", |c| ".#S".contains(c).then_some(c))
+}
@@ -1,3 +1,5 @@
+use crate::grammar;
+use common::parse::parse;
 use std::collections::HashSet;
 use indicatif::ProgressIterator;
 
@@ -53,7 +55,7 @@ pub fn run() {
 }
 
 fn evaluate(input: &str, steps: usize) -> usize {
-    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let grid = parse(grammar::garden(), input).unwrap_or_else(|error| panic!("{}", error));
     let start_index = input.find('S').unwrap();
     let width = grid[0].len();
     let height = grid.len();
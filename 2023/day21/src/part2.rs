@@ -1,3 +1,5 @@
+use crate::grammar;
+use common::parse::parse;
 use indicatif::ProgressIterator;
 use itertools::Itertools;
 use std::collections::HashSet;
@@ -60,7 +62,7 @@ pub fn run() {
 }
 
 fn evaluate(input: &str, steps: usize) -> usize {
-    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let grid = parse(grammar::garden(), input).unwrap_or_else(|error| panic!("{}", error));
     let start_index = input.find('S').unwrap();
     let width = grid[0].len() as isize;
     let height = grid.len() as isize;
@@ -0,0 +1,24 @@
+use crate::{Brick, Point};
+use common::parse::{integer, lines, tag, Parser};
+
+// `1,0,1`
+fn point<'a>() -> impl Parser<'a, Point> {
+    integer()
+        .then_ignore(tag(","))
+        .then(integer())
+        .then_ignore(tag(","))
+        .then(integer())
+        .map(|((x, y), z)| Point::new(x, y, z))
+}
+
+// `1,0,1~1,2,1`, both ends inclusive.
+pub(crate) fn brick<'a>() -> impl Parser<'a, Brick> {
+    point()
+        .then_ignore(tag("~"))
+        .then(point())
+        .map(|(start, end)| Brick::new(start, end))
+}
+
+pub(crate) fn snapshot<'a>() -> impl Parser<'a, Vec<Brick>> {
+    lines(brick())
+}
@@ -1,3 +1,6 @@
+mod grammar;
+
+use common::parse::parse;
 use std::collections::{HashSet, VecDeque};
 use itertools::Itertools;
 
@@ -13,11 +16,6 @@ impl Point {
         Self { x, y, z }
     }
 
-    fn from_str(s: &str) -> Self {
-        let parts: Vec<usize> = s.split(",").map(|x| x.parse().unwrap()).collect();
-        Self::new(parts[0], parts[1], parts[2])
-    }
-
     fn is_xy_between(&self, start: &Point, end: &Point) -> bool {
         (self.x >= start.x && self.x <= end.x)
             && (self.y >= start.y && self.y <= end.y)
@@ -35,13 +33,6 @@ impl Brick {
         Self { start, end }
     }
 
-    fn from_str(s: &str) -> Self {
-        let (start_str, end_str) = s.split_once("~").unwrap();
-        let start = Point::from_str(start_str);
-        let end = Point::from_str(end_str);
-        Self::new(start, end)
-    }
-
     fn supports<'a>(&self, bricks: &'a Vec<Brick>) -> Vec<&'a Brick> {
         bricks.iter().filter(|b| self.supports_brick(b)).collect()
     }
@@ -191,7 +182,7 @@ pub fn main() {
 }
 
 fn evaluate(input: String) -> (usize, usize) {
-    let bricks: Vec<Brick> = input.lines().map(|x| Brick::from_str(x)).collect();
+    let bricks = parse(grammar::snapshot(), &input).unwrap_or_else(|error| panic!("{}", error));
     let settled_bricks = bricks.settle();
     let part1 = settled_bricks
         .iter()
@@ -240,6 +231,10 @@ fn bricks_to_string(bricks: Vec<Brick>, x_side: bool) -> String {
 mod tests {
     use super::*;
 
+    fn read(text: &str) -> Brick {
+        parse(grammar::brick(), text).unwrap()
+    }
+
     #[test]
     fn test_evaluate() {
         let input = r"1,0,1~1,2,1
@@ -255,33 +250,33 @@ mod tests {
     #[test]
     fn test_inbounds() {
         let mut bricks = vec![
-            Brick::from_str("1,0,1~1,2,1"),
-            Brick::from_str("0,0,2~2,0,2"),
-            Brick::from_str("0,2,3~2,2,3"),
-            Brick::from_str("0,0,4~0,2,4"),
-            Brick::from_str("2,0,5~2,2,5"),
-            Brick::from_str("0,1,6~2,1,6"),
-            Brick::from_str("1,1,8~1,1,9"),
+            read("1,0,1~1,2,1"),
+            read("0,0,2~2,0,2"),
+            read("0,2,3~2,2,3"),
+            read("0,0,4~0,2,4"),
+            read("2,0,5~2,2,5"),
+            read("0,1,6~2,1,6"),
+            read("1,1,8~1,1,9"),
         ];
         bricks.sort_by_key(|b| b.end.z);
-        let brick_d = Brick::from_str("0,0,4~0,2,4");
+        let brick_d = read("0,0,4~0,2,4");
         let d_bounds = bricks.in_bounds(&brick_d);
         assert_eq!(d_bounds.len(), 2); // B and C
-        let brick_e = Brick::from_str("2,0,5~2,2,5");
+        let brick_e = read("2,0,5~2,2,5");
         let e_bounds = bricks.in_bounds(&brick_e);
         assert_eq!(e_bounds.len(), 2); // B and C
-        let brick_f = Brick::from_str("0,1,6~2,1,6");
+        let brick_f = read("0,1,6~2,1,6");
         let f_bounds = bricks.in_bounds(&brick_f);
         assert_eq!(f_bounds.len(), 3); // A, D and E
-        let brick_g = Brick::from_str("1,1,8~1,1,9");
+        let brick_g = read("1,1,8~1,1,9");
         let g_bounds = bricks.in_bounds(&brick_g);
         assert_eq!(g_bounds.len(), 2); // A and F
     }
 
     #[test]
     fn test_is_xy_between() {
-        let brick_a = Brick::from_str("1,0,1~1,2,1");
-        let brick_d = Brick::from_str("0,0,4~0,2,4");
+        let brick_a = read("1,0,1~1,2,1");
+        let brick_d = read("0,0,4~0,2,4");
 
         assert_eq!(brick_d.start.is_xy_between(&brick_a.start, &brick_a.end), false);
         assert_eq!(brick_d.end.is_xy_between(&brick_a.start, &brick_a.end), false);
@@ -291,13 +286,13 @@ mod tests {
 
     #[test]
     fn test_does_xy_collide() {
-        let brick_a = Brick::from_str("1,0,1~1,2,1");
-        let brick_b = Brick::from_str("0,0,2~2,0,2");
-        let brick_c = Brick::from_str("0,2,3~2,2,3");
-        let brick_d = Brick::from_str("0,0,4~0,2,4");
-        let brick_e = Brick::from_str("2,0,5~2,2,5");
-        let brick_f = Brick::from_str("0,1,6~2,1,6");
-        let brick_g = Brick::from_str("1,1,8~1,1,9");
+        let brick_a = read("1,0,1~1,2,1");
+        let brick_b = read("0,0,2~2,0,2");
+        let brick_c = read("0,2,3~2,2,3");
+        let brick_d = read("0,0,4~0,2,4");
+        let brick_e = read("2,0,5~2,2,5");
+        let brick_f = read("0,1,6~2,1,6");
+        let brick_g = read("1,1,8~1,1,9");
 
         let a_points = brick_a.points();
         let b_points = brick_b.points();
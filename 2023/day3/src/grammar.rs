@@ -0,0 +1,52 @@
+use common::parse::{integer, lines, satisfy, tag, Parser, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Item {
+    Number(u32, Span),
+    Symbol(char, Span),
+}
+
+// A number, a symbol or an empty `.` cell.
+fn item<'a>() -> impl Parser<'a, Option<Item>> {
+    let number = integer()
+        .spanned()
+        .map(|(value, span)| Item::Number(value, span));
+    let symbol = satisfy("symbol", |c| !c.is_ascii_digit() && !c.is_whitespace())
+        .spanned()
+        .map(|(glyph, span)| Item::Symbol(glyph, span));
+    tag(".").to(None).or(number.map(Some)).or(symbol.map(Some))
+}
+
+// `467..114..` and `...*......`: the items of every row, with the span of the row so
+// their columns can be taken from its start.
+pub(crate) fn schematic<'a>() -> impl Parser<'a, Vec<(Vec<Item>, Span)>> {
+    let row = item()
+        .repeated()
+        .map(|items| items.into_iter().flatten().collect());
+    lines(row.spanned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_schematic() {
+        let rows = parse(schematic(), "467..#\n..12*.").unwrap();
+        assert_eq!(
+            rows[0],
+            (
+                vec![
+                    Item::Number(467, Span::new(0, 3)),
+                    Item::Symbol('#', Span::new(5, 6))
+                ],
+                Span::new(0, 6)
+            )
+        );
+        assert_eq!(rows[1].0.len(), 2);
+        assert_eq!(rows[1].1, Span::new(7, 13));
+        let error = parse(schematic(), "1.2\n3 4").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::grammar::{self, Item};
+use common::parse::{parse, ParseError};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Number {
+    pub(crate) value: u32,
+    pub(crate) row: usize,
+    // Columns `start..end` of the digits.
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Symbol {
+    pub(crate) glyph: char,
+    pub(crate) row: usize,
+    pub(crate) column: usize,
+}
+
+// The engine schematic as a bipartite graph: every number is linked to the symbols in
+// the ring of cells around it, and every symbol to the numbers it touches.
+#[derive(Debug, Clone)]
+pub(crate) struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    number_symbols: Vec<Vec<usize>>,
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    pub(crate) fn parse(input: &str) -> Result<Schematic, ParseError> {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut symbol_at = HashMap::new();
+        for (row, (items, line)) in parse(grammar::schematic(), input)?.into_iter().enumerate() {
+            for item in items {
+                match item {
+                    Item::Number(value, span) => numbers.push(Number {
+                        value,
+                        row,
+                        start: span.start - line.start,
+                        end: span.end - line.start,
+                    }),
+                    Item::Symbol(glyph, span) => {
+                        let column = span.start - line.start;
+                        symbol_at.insert((row, column), symbols.len());
+                        symbols.push(Symbol { glyph, row, column });
+                    }
+                }
+            }
+        }
+
+        let mut symbol_numbers = vec![vec![]; symbols.len()];
+        let number_symbols = numbers
+            .iter()
+            .enumerate()
+            .map(|(index, number)| {
+                let found = ring(number)
+                    .filter_map(|cell| symbol_at.get(&cell).copied())
+                    .collect::<Vec<usize>>();
+                found
+                    .iter()
+                    .for_each(|symbol| symbol_numbers[*symbol].push(index));
+                found
+            })
+            .collect();
+        Ok(Schematic {
+            numbers,
+            symbols,
+            number_symbols,
+            symbol_numbers,
+        })
+    }
+
+    pub(crate) fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    // Numbers touching at least one symbol.
+    pub(crate) fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    pub(crate) fn part_number_sum(&self) -> u32 {
+        self.part_numbers().map(|number| number.value).sum()
+    }
+
+    pub(crate) fn adjacent_numbers(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|number| &self.numbers[*number])
+    }
+
+    // Symbols (by index) touching exactly `k` numbers. Gears are the `*` ones with k = 2.
+    pub(crate) fn symbols_with_exactly(&self, k: usize) -> impl Iterator<Item = usize> + '_ {
+        self.symbol_numbers
+            .iter()
+            .enumerate()
+            .filter(move |(_, numbers)| numbers.len() == k)
+            .map(|(symbol, _)| symbol)
+    }
+}
+
+// The cells around a number, clipped at the top and left edges.
+fn ring(number: &Number) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let rows = number.row.saturating_sub(1)..=number.row + 1;
+    let digits = number.start..number.end;
+    rows.flat_map(move |row| {
+        let digits = digits.clone();
+        (number.start.saturating_sub(1)..=number.end)
+            .filter(move |column| row != number.row || !digits.contains(column))
+            .map(move |column| (row, column))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_parse() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols().len(), 6);
+        assert_eq!(
+            schematic.numbers[0],
+            Number {
+                value: 467,
+                row: 0,
+                start: 0,
+                end: 3
+            }
+        );
+        assert_eq!(
+            schematic.symbols()[1],
+            Symbol {
+                glyph: '#',
+                row: 3,
+                column: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_part_numbers() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        assert_eq!(schematic.part_number_sum(), 4361);
+        assert!(schematic
+            .part_numbers()
+            .all(|number| number.value != 114 && number.value != 58));
+    }
+
+    #[test]
+    fn test_symbols_with_exactly() {
+        let schematic = Schematic::parse(EXAMPLE).unwrap();
+        let pairs = schematic
+            .symbols_with_exactly(2)
+            .map(|symbol| {
+                let glyph = schematic.symbols()[symbol].glyph;
+                let values = schematic
+                    .adjacent_numbers(symbol)
+                    .map(|number| number.value);
+                (glyph, values.collect::<Vec<u32>>())
+            })
+            .collect::<Vec<(char, Vec<u32>)>>();
+        assert_eq!(pairs, vec![('*', vec![467, 35]), ('*', vec![755, 598])]);
+        assert_eq!(schematic.symbols_with_exactly(1).count(), 4);
+        assert_eq!(schematic.symbols_with_exactly(3).count(), 0);
+    }
+
+    #[test]
+    fn test_edges_and_shared_symbols() {
+        // Numbers at the start and end of lines, and one symbol touching three numbers.
+        let schematic = Schematic::parse("12.3\n..*.\n4..5").unwrap();
+        assert_eq!(schematic.part_number_sum(), 12 + 3 + 5);
+        assert_eq!(
+            schematic.symbols_with_exactly(3).collect::<Vec<usize>>(),
+            vec![0]
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use crate::scratchcard::{Card, NumberSet};
+use common::parse::{integer, spaces1, tag, Parser};
+
+fn numbers<'a>() -> impl Parser<'a, Vec<u32>> {
+    integer().separated_by(spaces1())
+}
+
+// `Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53`
+pub(crate) fn card<'a>() -> impl Parser<'a, Card> {
+    tag("Card")
+        .then_ignore(spaces1())
+        .ignore_then(integer::<u32>())
+        .ignore_then(tag(":").padded())
+        .ignore_then(numbers())
+        .then_ignore(tag("|").padded())
+        .then(numbers())
+        .map(|(winning, numbers)| Card {
+            winning: NumberSet::new(&winning),
+            numbers,
+        })
+}
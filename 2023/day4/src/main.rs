@@ -1,5 +1,7 @@
+mod grammar;
 mod part1;
 mod part2;
+mod scratchcard;
 
 fn main() {
     part1::main();
@@ -1,74 +1,25 @@
-use std::string::ParseError;
-
-struct Card {
-    id: u32,
-    winning_numbers: Vec<u32>,
-    user_numbers: Vec<u32>,
-}
-
-impl Card {
-    fn get_winning_points(&self) -> u32 {
-        let mut power = 0;
-        for n in &self.winning_numbers {
-            if self.user_numbers.contains(n) {
-                power += 1;
-            }
-        }
-        let base: u32 = 2;
-        if power == 0 {
-            return 0;
-        }
-        return base.pow(power - 1);
-    }
-}
+use crate::scratchcard::Card;
+use common::parse::ParseError;
 
 pub fn main() {
-    let input = r"src\inputs\input.txt";
+    let input = "src/inputs/input.txt";
     let content = std::fs::read_to_string(input).unwrap();
-    let result = process(&content).unwrap();
-    println!("part1: {}", result);
-}
-
-fn process(input: &str) -> Result<u32, String> {
-    let lines = input.lines().collect::<Vec<_>>();
-    let card = lines
-        .iter()
-        .map(|l| parse_card(l))
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
-    let points = card.iter().map(|c| c.get_winning_points());
-    Ok(points.sum())
+    match process(&content).unwrap() {
+        Some(result) => println!("part1: {}", result),
+        None => println!("part1: too many points for a u32"),
+    }
 }
 
-fn parse_card(line: &str) -> Result<Card, ParseError> {
-    let mut parts = line.split(":");
-    let id = parts
-        .next()
-        .unwrap()
-        .trim()
-        .split_whitespace()
-        .nth(1)
-        .unwrap()
-        .parse::<u32>()
-        .unwrap();
-    let mut numbers = parts.next().unwrap().trim().split(" | ");
-    let winning_numbers = numbers
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .map(|n| n.parse::<u32>().unwrap())
-        .collect::<Vec<_>>();
-    let user_numbers = numbers
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .map(|n| n.parse::<u32>().unwrap())
-        .collect::<Vec<_>>();
-    Ok(Card {
-        id,
-        winning_numbers,
-        user_numbers,
-    })
+// None when a card's points, or their sum, overflow.
+fn process(input: &str) -> Result<Option<u32>, ParseError> {
+    let mut total = Some(0u32);
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let points = Card::parse(line)?.points();
+        total = total
+            .zip(points)
+            .and_then(|(total, points)| total.checked_add(points));
+    }
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -84,6 +35,6 @@ Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
 Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
 Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
         let result = process(input).unwrap();
-        assert_eq!(result, 13);
+        assert_eq!(result, Some(13));
     }
 }
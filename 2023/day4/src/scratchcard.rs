@@ -0,0 +1,214 @@
+use crate::grammar;
+use common::parse::{parse, ParseError};
+use std::collections::{HashSet, VecDeque};
+
+// The winning numbers of a card. Puzzle numbers are all below 100 and fit in a single
+// u128 bitmask; anything larger falls back to a hash set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NumberSet {
+    Bits(u128),
+    Hashed(HashSet<u32>),
+}
+
+impl NumberSet {
+    pub(crate) fn new(numbers: &[u32]) -> NumberSet {
+        if numbers.iter().all(|number| *number < 128) {
+            NumberSet::Bits(numbers.iter().fold(0, |bits, number| bits | 1 << number))
+        } else {
+            NumberSet::Hashed(numbers.iter().copied().collect())
+        }
+    }
+
+    pub(crate) fn contains(&self, number: u32) -> bool {
+        match self {
+            NumberSet::Bits(bits) => number < 128 && bits >> number & 1 == 1,
+            NumberSet::Hashed(numbers) => numbers.contains(&number),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Card {
+    pub(crate) winning: NumberSet,
+    pub(crate) numbers: Vec<u32>,
+}
+
+impl Card {
+    pub(crate) fn parse(line: &str) -> Result<Card, ParseError> {
+        parse(grammar::card(), line)
+    }
+
+    pub(crate) fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|number| self.winning.contains(**number))
+            .count()
+    }
+
+    // Part 1: one point for the first match, doubled for every other one. None once
+    // there are more than 32 matches and the points no longer fit.
+    pub(crate) fn points(&self) -> Option<u32> {
+        match self.matches() {
+            0 => Some(0),
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|shift| 1u32.checked_shl(shift)),
+        }
+    }
+}
+
+// Counts card instances one card at a time. `pending[i]` holds the copies already won
+// for the card `i` places ahead, so the window is never longer than the largest
+// number of matches and the card list itself is never kept.
+#[derive(Debug, Default)]
+pub(crate) struct Cascade {
+    pending: VecDeque<u64>,
+    total: u64,
+}
+
+impl Cascade {
+    pub(crate) fn new() -> Cascade {
+        Cascade::default()
+    }
+
+    // Takes the next card's number of matches and returns how many instances of it
+    // there are. Copies won past the last card are never collected. None once a count
+    // no longer fits in u64, which leaves the cascade unusable.
+    pub(crate) fn push(&mut self, matches: usize) -> Option<u64> {
+        let instances = self.pending.pop_front().unwrap_or(0).checked_add(1)?;
+        for ahead in 0..matches {
+            match self.pending.get_mut(ahead) {
+                Some(copies) => *copies = copies.checked_add(instances)?,
+                None => self.pending.push_back(instances),
+            }
+        }
+        self.total = self.total.checked_add(instances)?;
+        Some(instances)
+    }
+
+    pub(crate) fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+// Part 2 over any stream of card lines, None when the instances overflow.
+pub(crate) fn total_instances<I, S>(lines: I) -> Result<Option<u64>, ParseError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut cascade = Cascade::new();
+    for line in lines {
+        let line = line.as_ref();
+        if line.trim().is_empty() {
+            continue;
+        }
+        if cascade.push(Card::parse(line)?.matches()).is_none() {
+            return Ok(None);
+        }
+    }
+    Ok(Some(cascade.total()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_number_set() {
+        let small = NumberSet::new(&[0, 17, 99, 127]);
+        assert!(matches!(small, NumberSet::Bits(_)));
+        assert!(small.contains(99) && small.contains(0) && small.contains(127));
+        assert!(!small.contains(98) && !small.contains(1000));
+        let large = NumberSet::new(&[5, 1000]);
+        assert!(matches!(large, NumberSet::Hashed(_)));
+        assert!(large.contains(1000) && large.contains(5) && !large.contains(6));
+    }
+
+    #[test]
+    fn test_card() {
+        let cards = EXAMPLE
+            .lines()
+            .map(Card::parse)
+            .collect::<Result<Vec<Card>, ParseError>>()
+            .unwrap();
+        let matches = cards.iter().map(Card::matches).collect::<Vec<usize>>();
+        assert_eq!(matches, vec![4, 2, 2, 1, 0, 0]);
+        assert_eq!(
+            cards.iter().map(Card::points).sum::<Option<u32>>(),
+            Some(13)
+        );
+        let error = Card::parse("Card 1 41 | 41").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected),
+            (8, vec!["\":\"".to_string()])
+        );
+        let error = Card::parse("Card 1: 41 41").unwrap_err();
+        assert_eq!(
+            error.to_string().lines().next(),
+            Some("line 1, column 14: expected \"|\", found end of input")
+        );
+        assert_eq!(Card::parse("Card 1: 41 48|48").unwrap().matches(), 1);
+        let error = Card::parse("Card 1: 4x | 41").unwrap_err();
+        assert_eq!((error.column, error.found), (10, "\"x\"".to_string()));
+        let error = Card::parse("Card 1: 4294967296 | 41").unwrap_err();
+        assert_eq!(
+            (error.column, error.expected),
+            (9, vec!["number that fits in u32".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let card = |matches: u32| {
+            let numbers = (1..=matches)
+                .map(|n| n.to_string())
+                .collect::<Vec<String>>();
+            let numbers = numbers.join(" ");
+            Card::parse(&format!("Card 1: {} | {}", numbers, numbers)).unwrap()
+        };
+        assert_eq!(card(32).points(), Some(1 << 31));
+        assert_eq!(card(33).points(), None);
+        assert_eq!(card(200).points(), None);
+    }
+
+    #[test]
+    fn test_cascade() {
+        let mut cascade = Cascade::new();
+        let instances = [4, 2, 2, 1, 0, 0].map(|matches| cascade.push(matches));
+        assert_eq!(instances, [1, 2, 4, 8, 14, 1].map(Some));
+        assert_eq!(cascade.total(), 30);
+        assert_eq!(total_instances(EXAMPLE.lines()), Ok(Some(30)));
+        // Copies won past the last card are dropped.
+        assert_eq!(total_instances(["Card 1: 1 2 | 1 2"]), Ok(Some(1)));
+    }
+
+    #[test]
+    fn test_long_stream() {
+        // Every card wins the next one, so card n has n instances, and the window
+        // never grows past a single pending entry.
+        let mut cascade = Cascade::new();
+        for n in 1..=100_000u64 {
+            assert_eq!(cascade.push(1), Some(n));
+            assert!(cascade.pending.len() <= 1);
+        }
+        assert_eq!(cascade.total(), 100_000 * 100_001 / 2);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Two matches per card make the instances grow like the Fibonacci numbers.
+        let mut cascade = Cascade::new();
+        let first_overflow = (1..200).find(|_| cascade.push(2).is_none());
+        assert_eq!(first_overflow, Some(90));
+        let lines = (1..200).map(|n| format!("Card {}: 1 2 | 1 2", n));
+        assert_eq!(total_instances(lines), Ok(None));
+    }
+}
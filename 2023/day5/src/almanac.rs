@@ -0,0 +1,385 @@
+use crate::grammar;
+use common::parse::{parse, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum AlmanacError {
+    // Includes rules whose ranges run past u64::MAX.
+    Syntax(ParseError),
+    // Two maps leave the same category.
+    DuplicateMap(String),
+    // A map that cannot be reached from the seeds.
+    Unreachable(String),
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacError::Syntax(error) => write!(f, "{}", error),
+            AlmanacError::DuplicateMap(from) => write!(f, "more than one map from {:?}", from),
+            AlmanacError::Unreachable(from) => {
+                write!(f, "the map from {:?} is not reachable from the seeds", from)
+            }
+        }
+    }
+}
+
+// A function on u64 that adds a constant offset on each piece. `pieces` holds the
+// start of every piece with its offset, sorted, starting at 0; a piece runs up to the
+// next start, the last one to the end of u64.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Piecewise {
+    pieces: Vec<(u64, i128)>,
+}
+
+impl Piecewise {
+    pub(crate) fn identity() -> Piecewise {
+        Piecewise {
+            pieces: vec![(0, 0)],
+        }
+    }
+
+    // `destination source length` rules; where rules overlap the earlier one wins and
+    // values outside every rule map to themselves.
+    pub(crate) fn from_rules(rules: &[(u64, u64, u64)]) -> Piecewise {
+        let mut function = Piecewise::identity();
+        for (destination, source, length) in rules.iter().rev() {
+            let offset = *destination as i128 - *source as i128;
+            function.overlay(*source, source.checked_add(*length), offset);
+        }
+        function
+    }
+
+    fn piece_index(&self, x: u64) -> usize {
+        self.pieces.partition_point(|(start, _)| *start <= x) - 1
+    }
+
+    fn offset_at(&self, x: u64) -> i128 {
+        self.pieces[self.piece_index(x)].1
+    }
+
+    // The end of the piece at `index`, `None` for the last one.
+    fn end(&self, index: usize) -> Option<u64> {
+        self.pieces.get(index + 1).map(|(start, _)| *start)
+    }
+
+    // Sets the offset to `offset` on start..end, with `None` for no end.
+    fn overlay(&mut self, start: u64, end: Option<u64>, offset: i128) {
+        if end.is_some_and(|end| end <= start) {
+            return;
+        }
+        let after = end.map(|end| (end, self.offset_at(end)));
+        let mut pieces = self
+            .pieces
+            .iter()
+            .copied()
+            .filter(|(s, _)| *s < start)
+            .collect::<Vec<(u64, i128)>>();
+        pieces.push((start, offset));
+        if let Some((end, after)) = after {
+            pieces.push((end, after));
+            pieces.extend(self.pieces.iter().copied().filter(|(s, _)| *s > end));
+        }
+        self.pieces = pieces;
+        self.merge();
+    }
+
+    // Joins neighbouring pieces with the same offset.
+    fn merge(&mut self) {
+        self.pieces.dedup_by(|next, previous| next.1 == previous.1);
+    }
+
+    pub(crate) fn apply(&self, x: u64) -> u64 {
+        (x as i128 + self.offset_at(x)) as u64
+    }
+
+    // The image of `range`, as one range per piece it crosses, in domain order.
+    pub(crate) fn apply_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        let mut images = vec![];
+        let mut start = range.start;
+        while start < range.end {
+            let index = self.piece_index(start);
+            let end = self.end(index).map_or(range.end, |end| end.min(range.end));
+            let offset = self.pieces[index].1;
+            images.push((start as i128 + offset) as u64..(end as i128 + offset) as u64);
+            start = end;
+        }
+        images
+    }
+
+    // `next` after `self`, as a single piecewise function.
+    pub(crate) fn then(&self, next: &Piecewise) -> Piecewise {
+        let mut pieces = vec![];
+        for (index, (start, offset)) in self.pieces.iter().enumerate() {
+            let image_start = *start as i128 + offset;
+            let image_end = self.end(index).map(|end| end as i128 + offset);
+            pieces.push((*start, offset + next.offset_at(image_start as u64)));
+            for (break_start, next_offset) in next.pieces.iter() {
+                let break_start = *break_start as i128;
+                if break_start > image_start && image_end.is_none_or(|end| break_start < end) {
+                    pieces.push(((break_start - offset) as u64, offset + next_offset));
+                }
+            }
+        }
+        let mut function = Piecewise { pieces };
+        function.merge();
+        function
+    }
+
+    // Every x with f(x) = y, in increasing order.
+    pub(crate) fn inverse(&self, y: u64) -> Vec<u64> {
+        (0..self.pieces.len())
+            .filter_map(|index| {
+                let (start, offset) = self.pieces[index];
+                let x = y as i128 - offset;
+                let inside = x >= start as i128
+                    && self.end(index).is_none_or(|end| x < end as i128)
+                    && x <= u64::MAX as i128;
+                inside.then_some(x as u64)
+            })
+            .collect()
+    }
+}
+
+// One `X-to-Y map:` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Map {
+    pub(crate) from: String,
+    pub(crate) to: String,
+    pub(crate) function: Piecewise,
+}
+
+// The seeds and the maps from `seed` onwards, in chain order whatever order the
+// sections came in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Almanac {
+    pub(crate) seeds: Vec<u64>,
+    pub(crate) maps: Vec<Map>,
+}
+
+impl Almanac {
+    pub(crate) fn parse(input: &str) -> Result<Almanac, AlmanacError> {
+        let (seeds, sections) = parse(grammar::almanac(), input).map_err(AlmanacError::Syntax)?;
+        let mut by_source = HashMap::new();
+        for map in sections {
+            if by_source.contains_key(&map.from) {
+                return Err(AlmanacError::DuplicateMap(map.from));
+            }
+            by_source.insert(map.from.clone(), map);
+        }
+        let mut maps = vec![];
+        let mut category = "seed".to_string();
+        while let Some(map) = by_source.remove(&category) {
+            category = map.to.clone();
+            maps.push(map);
+        }
+        if let Some(from) = by_source.into_keys().min() {
+            return Err(AlmanacError::Unreachable(from));
+        }
+        Ok(Almanac { seeds, maps })
+    }
+
+    // The category at the end of the chain, usually `location`.
+    pub(crate) fn target(&self) -> &str {
+        self.maps.last().map_or("seed", |map| &map.to)
+    }
+
+    // Every map composed into a single seed-to-target function.
+    pub(crate) fn composed(&self) -> Piecewise {
+        self.maps
+            .iter()
+            .fold(Piecewise::identity(), |function, map| {
+                function.then(&map.function)
+            })
+    }
+
+    // Part 2 reads the seeds line as `start length` pairs.
+    pub(crate) fn seed_ranges(&self) -> Vec<Range<u64>> {
+        self.seeds
+            .chunks(2)
+            .filter(|pair| pair.len() == 2)
+            .map(|pair| pair[0]..pair[0].saturating_add(pair[1]))
+            .collect()
+    }
+
+    pub(crate) fn lowest_location(&self) -> Option<u64> {
+        let function = self.composed();
+        self.seeds.iter().map(|seed| function.apply(*seed)).min()
+    }
+
+    pub(crate) fn lowest_location_of_ranges(&self) -> Option<u64> {
+        let function = self.composed();
+        self.seed_ranges()
+            .into_iter()
+            .flat_map(|range| function.apply_range(range))
+            .map(|range| range.start)
+            .min()
+    }
+
+    // Every seed that ends up at `location`, whether or not the almanac lists it.
+    pub(crate) fn seeds_for_location(&self, location: u64) -> Vec<u64> {
+        self.composed().inverse(location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+    // Applying the maps one by one, the way the puzzle describes it.
+    fn step_by_step(almanac: &Almanac, seed: u64) -> u64 {
+        almanac
+            .maps
+            .iter()
+            .fold(seed, |value, map| map.function.apply(value))
+    }
+
+    #[test]
+    fn test_piecewise() {
+        let function = Piecewise::from_rules(&[(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(function.apply(79), 81);
+        assert_eq!(function.apply(98), 50);
+        assert_eq!(function.apply(100), 100);
+        assert_eq!(function.apply(u64::MAX), u64::MAX);
+        assert_eq!(function.apply_range(40..60), vec![40..50, 52..62]);
+        assert_eq!(function.inverse(50), vec![98]);
+        assert_eq!(function.inverse(99), vec![97]);
+        assert_eq!(function.inverse(10), vec![10]);
+        // Earlier rules win where rules overlap.
+        let overlapping = Piecewise::from_rules(&[(0, 10, 5), (100, 0, 20)]);
+        assert_eq!(overlapping.apply(12), 2);
+        assert_eq!(overlapping.apply(16), 116);
+        assert_eq!(Piecewise::from_rules(&[(5, 5, 10)]), Piecewise::identity());
+    }
+
+    #[test]
+    fn test_then() {
+        let first = Piecewise::from_rules(&[(50, 98, 2), (52, 50, 48)]);
+        let second = Piecewise::from_rules(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]);
+        let composed = first.then(&second);
+        for x in 0..200 {
+            assert_eq!(composed.apply(x), second.apply(first.apply(x)));
+        }
+        assert_eq!(first.then(&Piecewise::identity()), first);
+        assert_eq!(Piecewise::identity().then(&first), first);
+    }
+
+    #[test]
+    fn test_almanac() {
+        let almanac = Almanac::parse(EXAMPLE).unwrap();
+        assert_eq!(almanac.maps.len(), 7);
+        assert_eq!(almanac.target(), "location");
+        let composed = almanac.composed();
+        for seed in 0..200 {
+            assert_eq!(composed.apply(seed), step_by_step(&almanac, seed));
+        }
+        assert_eq!(composed.apply(79), 82);
+        assert_eq!(almanac.lowest_location(), Some(35));
+        assert_eq!(almanac.seed_ranges(), vec![79..93, 55..68]);
+        assert_eq!(almanac.lowest_location_of_ranges(), Some(46));
+        assert_eq!(almanac.seeds_for_location(46), vec![82]);
+        for location in 0..200 {
+            for seed in almanac.seeds_for_location(location) {
+                assert_eq!(composed.apply(seed), location);
+            }
+        }
+    }
+
+    #[test]
+    fn test_any_chain() {
+        // Sections in any order and with any names, as long as they chain from seeds.
+        let almanac = Almanac::parse(
+            "seeds: 1 2 3
+
+dirt-to-rock map:
+100 0 10
+
+seed-to-dirt map:
+5 1 2",
+        )
+        .unwrap();
+        assert_eq!(almanac.target(), "rock");
+        assert_eq!(almanac.lowest_location(), Some(103));
+        assert_eq!(almanac.seeds_for_location(106), vec![2, 6, 106]);
+        let no_maps = Almanac::parse("seeds: 4 1").unwrap();
+        assert_eq!(no_maps.lowest_location_of_ranges(), Some(4));
+    }
+
+    #[test]
+    fn test_errors() {
+        let syntax = |input: &str| match Almanac::parse(input) {
+            Err(AlmanacError::Syntax(error)) => (error.line, error.column, error.expected),
+            other => panic!("expected a syntax error, got {:?}", other),
+        };
+        let expected = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        assert_eq!(syntax("1 2\n\n"), (1, 1, expected(&["\"seeds:\""])));
+        assert_eq!(
+            syntax("seeds: 1\n\nseed to soil:\n1 2 3"),
+            (3, 5, expected(&["\"-to-\""]))
+        );
+        assert_eq!(
+            syntax("seeds: 1\n\nseed-to-soil map:\n1 2"),
+            (4, 4, expected(&["space"]))
+        );
+        assert_eq!(
+            syntax("seeds: 1\n\nseed-to-soil map:\n1 18446744073709551615 2"),
+            (4, 1, expected(&["rule whose ranges fit in u64"]))
+        );
+        assert_eq!(syntax("seeds: x"), (1, 8, expected(&["number"])));
+        assert_eq!(syntax("seeds: 4x"), (1, 9, expected(&["end of input"])));
+        assert_eq!(
+            syntax("seeds: 1 18446744073709551616"),
+            (1, 10, expected(&["number that fits in u64"]))
+        );
+        let trailing = Almanac::parse("seeds: 1 2 \n\nseed-to-soil map:\n50 98 2 \n").unwrap();
+        assert_eq!(trailing.seeds, vec![1, 2]);
+        assert_eq!(
+            syntax("seeds: 1\n\nseed-to-soil map:\n1 2 x"),
+            (4, 5, expected(&["number"]))
+        );
+        assert_eq!(
+            Almanac::parse("seeds: 1\n\nseed-to-a map:\n1 2 3\n\nseed-to-b map:\n1 2 3"),
+            Err(AlmanacError::DuplicateMap("seed".to_string()))
+        );
+        assert_eq!(
+            Almanac::parse("seeds: 1\n\nseed-to-a map:\n1 2 3\n\nb-to-c map:\n1 2 3"),
+            Err(AlmanacError::Unreachable("b".to_string()))
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::almanac::{Map, Piecewise};
+use common::parse::{integer, newline, sections, spaces1, tag, word, Parser};
+
+// `seeds: 79 14 55 13`
+fn seeds<'a>() -> impl Parser<'a, Vec<u64>> {
+    tag("seeds:").ignore_then(integer().padded().repeated1())
+}
+
+// `50 98 2`: destination start, source start and length.
+fn rule<'a>() -> impl Parser<'a, (u64, u64, u64)> {
+    integer()
+        .then_ignore(spaces1())
+        .then(integer())
+        .then_ignore(spaces1())
+        .then(integer())
+        .try_map(|((destination, source), length): ((u64, u64), u64)| {
+            match (destination.checked_add(length), source.checked_add(length)) {
+                (Some(_), Some(_)) => Ok((destination, source, length)),
+                _ => Err("rule whose ranges fit in u64".to_string()),
+            }
+        })
+}
+
+// `seed-to-soil map:` followed by one rule per line.
+fn map<'a>() -> impl Parser<'a, Map> {
+    word()
+        .then_ignore(tag("-to-"))
+        .then(word())
+        .then_ignore(tag(" map:"))
+        .then_ignore(newline())
+        .then(rule().separated_by(newline()))
+        .map(|((from, to), rules)| Map {
+            from: from.to_string(),
+            to: to.to_string(),
+            function: Piecewise::from_rules(&rules),
+        })
+}
+
+// The seeds line, then the maps in any order.
+pub(crate) fn almanac<'a>() -> impl Parser<'a, (Vec<u64>, Vec<Map>)> {
+    let maps = newline().repeated1().ignore_then(sections(map()));
+    seeds()
+        .then(maps.optional())
+        .map(|(seeds, maps)| (seeds, maps.unwrap_or_default()))
+}
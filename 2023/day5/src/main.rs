@@ -1,8 +1,37 @@
+mod almanac;
+mod grammar;
 mod part1;
 mod part2;
-mod part2_optimised;
 
 fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    // `cargo run -- --seed-for 46` lists the seeds that end up at location 46.
+    if let Some(index) = args.iter().position(|arg| arg == "--seed-for") {
+        let location = args
+            .get(index + 1)
+            .and_then(|location| location.parse::<u64>().ok())
+            .expect("--seed-for takes a location");
+        let content = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+        let almanac = almanac::Almanac::parse(&content).unwrap();
+        let ranges = almanac.seed_ranges();
+        for seed in almanac.seeds_for_location(location) {
+            let listed = if almanac.seeds.contains(&seed) {
+                " (listed seed)"
+            } else if ranges.iter().any(|range| range.contains(&seed)) {
+                " (in a seed range)"
+            } else {
+                ""
+            };
+            println!(
+                "seed {} -> {} {}{}",
+                seed,
+                almanac.target(),
+                location,
+                listed
+            );
+        }
+        return;
+    }
     part1::main();
-    part2_optimised::main();
+    part2::main();
 }
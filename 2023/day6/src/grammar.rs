@@ -0,0 +1,53 @@
+use common::parse::{integer, newline, tag, take_while1, Parser};
+use num_bigint::BigUint;
+
+// `Time:      7  15   30` over `Distance:  9  40  200`, with `value` read from every
+// column.
+fn sheet<'a, T, P: Parser<'a, T>>(value: impl Fn() -> P) -> impl Parser<'a, (Vec<T>, Vec<T>)> {
+    let row = |name| tag(name).ignore_then(value().padded().repeated1());
+    row("Time:")
+        .then_ignore(newline())
+        .then(row("Distance:"))
+        .try_map(|(times, distances)| {
+            if times.len() == distances.len() {
+                Ok((times, distances))
+            } else {
+                Err(format!("{} distances", times.len()))
+            }
+        })
+}
+
+// Part 1: one race per column.
+pub(crate) fn races<'a>() -> impl Parser<'a, Vec<(u64, u64)>> {
+    sheet(integer).map(|(times, distances)| times.into_iter().zip(distances).collect())
+}
+
+// Part 2: the kerning is ignored, so all the digits of a row make up a single number.
+pub(crate) fn race<'a>() -> impl Parser<'a, (BigUint, BigUint)> {
+    let digits = || take_while1("digit", |c| c.is_ascii_digit());
+    let number = |columns: Vec<&str>| columns.concat().parse::<BigUint>().unwrap();
+    sheet(digits).map(move |(times, distances)| (number(times), number(distances)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_sheet() {
+        let input = "Time:      7  15\nDistance:  9  40\n";
+        assert_eq!(parse(races(), input), Ok(vec![(7, 9), (15, 40)]));
+        let (time, distance) = parse(race(), input).unwrap();
+        assert_eq!(
+            (time, distance),
+            (BigUint::from(715u32), BigUint::from(940u32))
+        );
+        let trailing = "Time: 7 15 \nDistance: 9 40\t\n";
+        assert_eq!(parse(races(), trailing), Ok(vec![(7, 9), (15, 40)]));
+        let (time, _) = parse(race(), trailing).unwrap();
+        assert_eq!(time, BigUint::from(715u32));
+        let error = parse(races(), "Time: 7 15\nDistance: 9").unwrap_err();
+        assert_eq!(error.expected, vec!["2 distances".to_string()]);
+    }
+}
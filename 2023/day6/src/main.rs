@@ -1,5 +1,7 @@
+mod grammar;
 mod part1;
 mod part2;
+mod race;
 
 fn main() {
     part1::main();
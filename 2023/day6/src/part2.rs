@@ -1,37 +1,23 @@
+use crate::grammar;
+use crate::race::{ways_to_win, ways_to_win_big};
+use common::parse::parse;
+use num_bigint::BigUint;
+
 pub fn main() {
-    let input_file = r"src\inputs\input.txt";
+    let input_file = "src/inputs/input.txt";
     let content = std::fs::read_to_string(input_file).unwrap();
     let result = process(&content);
     println!("Part 2: {}", result);
 }
 
-fn process(input: &str) -> usize {
-    let mut lines = input.lines();
-    let time = get_line_value(lines.next().unwrap());
-    let record_distance = get_line_value(lines.next().unwrap());
-    let possible_winning_ranges = get_possible_winning_ranges(&time, &record_distance);
-    let wining_range_count = possible_winning_ranges.1 - possible_winning_ranges.0 + 1;
-    wining_range_count
-}
-
-fn get_line_value(line: &str) -> usize {
-    line.split_whitespace()
-        .skip(1)
-        .collect::<Vec<_>>()
-        .join("")
-        .parse()
-        .unwrap()
-}
-
-fn get_possible_winning_ranges(time: &usize, record_distance: &usize) -> (usize, usize) {
-    // t * (time - t) = record_distance + 1
-    // t^2 - time * t + record_distance + 1 = 0
-    // t = (time +- sqrt(time^2 - 4 * record_distance)) / 2
-    let discriminant = time.pow(2) - 4 * (record_distance + 1);
-    let floored_sqrt_discriminant: f64 = (discriminant as f64).sqrt();
-    let t1 = ((time.clone() as f64) - floored_sqrt_discriminant) / 2.0;
-    let t2 = ((time.clone() as f64) + floored_sqrt_discriminant) / 2.0;
-    (t1.ceil() as usize, t2.floor() as usize)
+// Uses the u64 solver whenever both numbers fit, and big integers otherwise.
+fn process(input: &str) -> BigUint {
+    let (time, record_distance) =
+        parse(grammar::race(), input).unwrap_or_else(|error| panic!("{}", error));
+    match (u64::try_from(&time), u64::try_from(&record_distance)) {
+        (Ok(time), Ok(record_distance)) => BigUint::from(ways_to_win(time, record_distance)),
+        _ => ways_to_win_big(&time, &record_distance),
+    }
 }
 
 #[cfg(test)]
@@ -43,6 +29,16 @@ mod tests {
         let input = "Time:      7  15   30
 Distance:  9  40  200";
         let result = process(input);
-        assert_eq!(result, 71503);
+        assert_eq!(result, BigUint::from(71503u32));
+    }
+
+    #[test]
+    fn test_big_race() {
+        let input = "Time:      7  15   30 123456789012345
+Distance:  9  40  200 0";
+        let time = "71530123456789012345".parse::<BigUint>().unwrap();
+        let record = "9402000".parse::<BigUint>().unwrap();
+        assert_eq!(process(input), ways_to_win_big(&time, &record));
+        assert!(process(input) > BigUint::from(u64::MAX));
     }
 }
@@ -0,0 +1,152 @@
+use num_bigint::BigUint;
+use std::ops::Range;
+
+// Holding the button for `t` ms of a `time` ms race covers t * (time - t) mm, which
+// beats `record` strictly between the roots of t^2 - time * t + record = 0:
+// (time -+ sqrt(time^2 - 4 * record)) / 2. The square root is taken on integers and
+// the boundary nudged until it is exact; the winning holds are symmetric around
+// time / 2, so the upper end follows from the lower one.
+
+fn wins(time: u128, record: u128, hold: u128) -> bool {
+    hold * (time - hold) > record
+}
+
+// Every hold that beats the record, empty when none does. Squares of u64 values fit in
+// u128, so nothing here can overflow.
+pub(crate) fn winning_holds(time: u64, record: u64) -> Range<u64> {
+    let (time, record) = (time as u128, record as u128);
+    // The best hold is time / 2; for odd times it falls short of time^2 / 4.
+    if !wins(time, record, time / 2) {
+        return 0..0;
+    }
+    let root = (time * time - 4 * record).isqrt();
+    let mut low = (time - root) / 2;
+    while !wins(time, record, low) {
+        low += 1;
+    }
+    while low > 0 && wins(time, record, low - 1) {
+        low -= 1;
+    }
+    low as u64..(time - low + 1) as u64
+}
+
+pub(crate) fn ways_to_win(time: u64, record: u64) -> u64 {
+    let holds = winning_holds(time, record);
+    holds.end - holds.start
+}
+
+fn wins_big(time: &BigUint, record: &BigUint, hold: &BigUint) -> bool {
+    hold * (time - hold) > *record
+}
+
+// The same for races too long for u64, e.g. when the kerning is taken out of a long
+// input.
+pub(crate) fn winning_holds_big(time: &BigUint, record: &BigUint) -> Range<BigUint> {
+    if !wins_big(time, record, &(time / 2u32)) {
+        return BigUint::ZERO..BigUint::ZERO;
+    }
+    let root = (time * time - record * 4u32).sqrt();
+    let mut low: BigUint = (time - root) / 2u32;
+    while !wins_big(time, record, &low) {
+        low += 1u32;
+    }
+    while low > BigUint::ZERO && wins_big(time, record, &(&low - 1u32)) {
+        low -= 1u32;
+    }
+    let end = time - &low + 1u32;
+    low..end
+}
+
+pub(crate) fn ways_to_win_big(time: &BigUint, record: &BigUint) -> BigUint {
+    let holds = winning_holds_big(time, record);
+    holds.end - holds.start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn brute_force(time: u64, record: u64) -> Vec<u64> {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > record)
+            .collect()
+    }
+
+    #[test]
+    fn test_winning_holds() {
+        assert_eq!(winning_holds(7, 9), 2..6);
+        assert_eq!(winning_holds(15, 40), 4..12);
+        assert_eq!(winning_holds(30, 200), 11..20);
+        assert_eq!(ways_to_win(71530, 940200), 71503);
+        // The best hold only ties the record, and beyond that the record is out of reach.
+        assert_eq!(winning_holds(6, 9), 0..0);
+        assert_eq!(winning_holds(6, 100), 0..0);
+        assert_eq!(winning_holds(0, 0), 0..0);
+        assert_eq!(winning_holds(2, 0), 1..2);
+        // Odd times peak at (time^2 - 1) / 4, just below where the roots meet.
+        assert_eq!(winning_holds(7, 12), 0..0);
+        assert_eq!(ways_to_win(5, 6), 0);
+        assert_eq!(winning_holds(7, 11), 3..5);
+    }
+
+    #[test]
+    fn test_beyond_f64() {
+        // time^2 - 4 * record is 4 here, so the winning range is the single middle hold,
+        // while time^2 as an f64 is too coarse to see that difference at all.
+        let time = (1 << 33) - 2;
+        let half = time / 2;
+        assert_eq!(winning_holds(time, half * half - 1), half..half + 1);
+        assert_eq!(ways_to_win(u64::MAX, 0), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_big() {
+        let time = "123456789012345678901234567890".parse::<BigUint>().unwrap();
+        let half = &time / 2u32;
+        let record = &half * &half - 1u32;
+        assert_eq!(winning_holds_big(&time, &record), half.clone()..half + 1u32);
+        assert_eq!(
+            ways_to_win_big(&BigUint::from(71530u32), &BigUint::from(940200u32)),
+            BigUint::from(71503u32)
+        );
+        assert_eq!(
+            ways_to_win_big(&BigUint::from(6u32), &BigUint::from(9u32)),
+            BigUint::ZERO
+        );
+        assert_eq!(
+            ways_to_win_big(&BigUint::from(7u32), &BigUint::from(12u32)),
+            BigUint::ZERO
+        );
+        assert_eq!(
+            ways_to_win_big(&BigUint::from(5u32), &BigUint::from(6u32)),
+            BigUint::ZERO
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn matches_brute_force(time in 0u64..300, record in 0u64..25_000) {
+            let expected = brute_force(time, record);
+            let holds = winning_holds(time, record);
+            prop_assert_eq!(holds.clone().collect::<Vec<u64>>(), expected);
+            let big = winning_holds_big(&BigUint::from(time), &BigUint::from(record));
+            prop_assert_eq!(big.start, BigUint::from(holds.start));
+            prop_assert_eq!(big.end, BigUint::from(holds.end));
+        }
+
+        #[test]
+        fn boundaries_are_exact(time in 0u64..u64::MAX, record in 0u64..u64::MAX) {
+            let holds = winning_holds(time, record);
+            let (time, record) = (time as u128, record as u128);
+            if holds.is_empty() {
+                prop_assert!((time / 2) * (time - time / 2) <= record);
+            } else {
+                let (low, high) = (holds.start as u128, holds.end as u128 - 1);
+                prop_assert!(wins(time, record, low) && wins(time, record, high));
+                prop_assert!(low == 0 || !wins(time, record, low - 1));
+                prop_assert!(high == time || !wins(time, record, high + 1));
+            }
+        }
+    }
+}
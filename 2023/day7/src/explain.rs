@@ -0,0 +1,121 @@
+use crate::rules::{Hand, Rules};
+use std::fmt::Write;
+
+// Why `hand` ranks above `previous`: the type, or else the first card position (from
+// 1) that differs, or else the hand length when one hand extends the other.
+fn tie_break(rules: &Rules, previous: &Hand, hand: &Hand) -> String {
+    let (previous_type, hand_type) = (rules.hand_type(previous), rules.hand_type(hand));
+    if previous_type != hand_type {
+        return format!("type > {:?}", previous_type);
+    }
+    let differing = hand
+        .cards
+        .iter()
+        .zip(&previous.cards)
+        .position(|(card, previous_card)| card != previous_card);
+    match differing {
+        Some(position) => format!(
+            "card {}: {} > {}",
+            position + 1,
+            rules.render(&hand.cards[position..=position]),
+            rules.render(&previous.cards[position..=position])
+        ),
+        None if hand.cards.len() != previous.cards.len() => {
+            format!("length: {} > {}", hand.cards.len(), previous.cards.len())
+        }
+        None => "identical".to_string(),
+    }
+}
+
+// One row per hand from weakest to strongest, with the type, the cards after joker
+// substitution and what separated it from the hand ranked just below.
+pub(crate) fn explain(rules: &Rules, hands: Vec<Hand>) -> String {
+    let ranked = rules.rank(hands);
+    let mut table = format!(
+        "{:>5}  {:<7} {:<12} {:<11} {:>6} {:>10}  {}\n",
+        "Rank", "Hand", "Type", "Substituted", "Bid", "Winnings", "Beats previous by"
+    );
+    let mut total = 0;
+    for (index, hand) in ranked.iter().enumerate() {
+        let winnings = hand.bid * (index + 1);
+        total += winnings;
+        let reason = match index {
+            0 => "-".to_string(),
+            _ => tie_break(rules, &ranked[index - 1], hand),
+        };
+        writeln!(
+            table,
+            "{:>5}  {:<7} {:<12} {:<11} {:>6} {:>10}  {}",
+            index + 1,
+            rules.render(&hand.cards),
+            format!("{:?}", rules.hand_type(hand)),
+            rules.render(&rules.substitute(hand)),
+            hand.bid,
+            winnings,
+            reason
+        )
+        .unwrap();
+    }
+    writeln!(table, "Total winnings: {}", total).unwrap();
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_explain_jokers() {
+        let rules = Rules::jokers();
+        let table = explain(&rules, rules.parse_hands(EXAMPLE).unwrap());
+        let rows = table.lines().collect::<Vec<&str>>();
+        assert_eq!(rows.len(), 7);
+        assert_eq!(
+            rows[1],
+            "    1  32T3K   OnePair      32T3K          765        765  -"
+        );
+        assert_eq!(
+            rows[2],
+            "    2  KK677   TwoPairs     KK677           28         56  type > OnePair"
+        );
+        assert_eq!(
+            rows[4],
+            "    4  QQQJA   FourOfAKind  QQQQA          483       1932  card 1: Q > T"
+        );
+        assert_eq!(
+            rows[5],
+            "    5  KTJJT   FourOfAKind  KTTTT          220       1100  card 1: K > Q"
+        );
+        assert_eq!(rows[6], "Total winnings: 5905");
+    }
+
+    #[test]
+    fn test_tie_break() {
+        let rules = Rules::standard();
+        let hands = rules.parse_hands("KK677 28\nKTJJT 220\nKTJJT 1").unwrap();
+        assert_eq!(tie_break(&rules, &hands[1], &hands[0]), "card 2: K > T");
+        assert_eq!(tie_break(&rules, &hands[1], &hands[2]), "identical");
+    }
+
+    #[test]
+    fn test_tie_break_different_lengths() {
+        let rules = Rules::new("ABC");
+        let hands = rules.parse_hands("A 1\nAB 2\nAC 3").unwrap();
+        assert_eq!(tie_break(&rules, &hands[0], &hands[1]), "length: 2 > 1");
+        assert_eq!(tie_break(&rules, &hands[1], &hands[2]), "card 2: C > B");
+        assert_eq!(
+            rules
+                .rank(hands)
+                .iter()
+                .map(|hand| hand.bid)
+                .collect::<Vec<usize>>(),
+            vec![1, 2, 3]
+        );
+    }
+}
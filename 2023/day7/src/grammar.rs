@@ -0,0 +1,11 @@
+use crate::rules::{Hand, Rules};
+use common::parse::{integer, spaces1, take_while1, Parser};
+
+// `32T3K 765`: the cards, checked against `rules`, then the bid.
+pub(crate) fn hand<'a>(rules: &'a Rules) -> impl Parser<'a, Hand> {
+    take_while1("cards", |c| !c.is_whitespace())
+        .try_map(|text| rules.cards(text))
+        .then_ignore(spaces1())
+        .then(integer())
+        .map(|(cards, bid)| Hand { cards, bid })
+}
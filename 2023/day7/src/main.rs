@@ -1,9 +1,24 @@
+mod explain;
+mod grammar;
 mod part1;
-mod part1_copy;
 mod part2;
+mod rules;
 
 fn main() {
-    // part1::main();
-    part1_copy::main();
+    part1::main();
     part2::main();
+
+    // `cargo run -- --explain` prints the ranked part 1 hands, `--explain-jokers` the
+    // part 2 ones
+    let args = std::env::args().collect::<Vec<String>>();
+    for (flag, rules) in [
+        ("--explain", rules::Rules::standard()),
+        ("--explain-jokers", rules::Rules::jokers()),
+    ] {
+        if args.iter().any(|arg| arg == flag) {
+            let contents = std::fs::read_to_string("src/input/input.txt").unwrap();
+            let hands = rules.parse_hands(&contents).unwrap();
+            print!("{}", explain::explain(&rules, hands));
+        }
+    }
 }
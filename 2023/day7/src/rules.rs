@@ -0,0 +1,234 @@
+use crate::grammar;
+use common::parse::{lines, parse, ParseError};
+
+// Weakest first, so the derived `Ord` ranks hands directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum HandType {
+    HighCard,
+    OnePair,
+    TwoPairs,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    // From the card counts sorted in decreasing order.
+    fn from_counts(counts: &[usize]) -> HandType {
+        match counts {
+            [first, ..] if *first >= 5 => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, second, ..] if *second >= 2 => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPairs,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+// Cards are stored as their strength, the index in the rules' card order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct Hand {
+    pub(crate) cards: Vec<usize>,
+    pub(crate) bid: usize,
+}
+
+// How a game of Camel Cards is scored: the card order from weakest to strongest, the
+// cards that act as jokers, and optionally a fixed hand size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Rules {
+    order: Vec<char>,
+    wildcards: Vec<bool>,
+    hand_size: Option<usize>,
+}
+
+impl Rules {
+    pub(crate) fn new(order: &str) -> Rules {
+        let order = order.chars().collect::<Vec<char>>();
+        Rules {
+            wildcards: vec![false; order.len()],
+            order,
+            hand_size: None,
+        }
+    }
+
+    // Every card in `wildcards` must also be part of the card order.
+    pub(crate) fn wildcards(mut self, wildcards: &str) -> Rules {
+        for card in wildcards.chars() {
+            let strength = self
+                .strength(card)
+                .unwrap_or_else(|| panic!("Wildcard {:?} is not in the card order", card));
+            self.wildcards[strength] = true;
+        }
+        self
+    }
+
+    pub(crate) fn hand_size(mut self, hand_size: usize) -> Rules {
+        self.hand_size = Some(hand_size);
+        self
+    }
+
+    // Part 1: plain cards, five to a hand.
+    pub(crate) fn standard() -> Rules {
+        Rules::new("23456789TJQKA").hand_size(5)
+    }
+
+    // Part 2: J is a joker and the weakest card on its own.
+    pub(crate) fn jokers() -> Rules {
+        Rules::new("J23456789TQKA").wildcards("J").hand_size(5)
+    }
+
+    fn strength(&self, card: char) -> Option<usize> {
+        self.order.iter().position(|c| *c == card)
+    }
+
+    // Card strengths back to their characters.
+    pub(crate) fn render(&self, cards: &[usize]) -> String {
+        cards.iter().map(|card| self.order[*card]).collect()
+    }
+
+    // The strengths of a hand's cards, or what the rules expected instead.
+    pub(crate) fn cards(&self, text: &str) -> Result<Vec<usize>, String> {
+        let order = self.order.iter().collect::<String>();
+        let cards = text
+            .chars()
+            .map(|card| self.strength(card))
+            .collect::<Option<Vec<usize>>>();
+        match (cards, self.hand_size) {
+            (Some(cards), None) => Ok(cards),
+            (Some(cards), Some(size)) if cards.len() == size => Ok(cards),
+            (_, None) => Err(format!("cards from {}", order)),
+            (_, Some(size)) => Err(format!("{} cards from {}", size, order)),
+        }
+    }
+
+    pub(crate) fn parse_hands(&self, input: &str) -> Result<Vec<Hand>, ParseError> {
+        parse(lines(grammar::hand(self)), input)
+    }
+
+    // The hand with every wildcard turned into the most common other card (the
+    // strongest one on ties), which is always the best type jokers can make.
+    pub(crate) fn substitute(&self, hand: &Hand) -> Vec<usize> {
+        let mut counts = vec![0; self.order.len()];
+        for card in hand.cards.iter().filter(|card| !self.wildcards[**card]) {
+            counts[*card] += 1;
+        }
+        let target = (0..counts.len())
+            .max_by_key(|card| (counts[*card], *card))
+            .unwrap_or_default();
+        hand.cards
+            .iter()
+            .map(|card| if self.wildcards[*card] { target } else { *card })
+            .collect()
+    }
+
+    pub(crate) fn hand_type(&self, hand: &Hand) -> HandType {
+        let mut counts = vec![0; self.order.len()];
+        for card in self.substitute(hand) {
+            counts[card] += 1;
+        }
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        HandType::from_counts(&counts)
+    }
+
+    // Type first, then card by card from the left using the card order. Jokers keep
+    // their own strength for the tie-break.
+    pub(crate) fn sort_key(&self, hand: &Hand) -> (HandType, Vec<usize>) {
+        (self.hand_type(hand), hand.cards.clone())
+    }
+
+    // Hands from weakest to strongest, so a hand's rank is its index + 1.
+    pub(crate) fn rank(&self, mut hands: Vec<Hand>) -> Vec<Hand> {
+        hands.sort_by_cached_key(|hand| self.sort_key(hand));
+        hands
+    }
+
+    pub(crate) fn total_winnings(&self, hands: Vec<Hand>) -> usize {
+        self.rank(hands)
+            .iter()
+            .enumerate()
+            .map(|(index, hand)| hand.bid * (index + 1))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::Span;
+
+    // `32T3K 765`
+    fn parse_hand(rules: &Rules, line: &str) -> Result<Hand, ParseError> {
+        parse(grammar::hand(rules), line)
+    }
+
+    fn hand_type(rules: &Rules, cards: &str) -> HandType {
+        rules.hand_type(&parse_hand(rules, &format!("{} 0", cards)).unwrap())
+    }
+
+    #[test]
+    fn test_hand_types() {
+        let rules = Rules::standard();
+        assert_eq!(hand_type(&rules, "23456"), HandType::HighCard);
+        assert_eq!(hand_type(&rules, "22222"), HandType::FiveOfAKind);
+        assert_eq!(hand_type(&rules, "22322"), HandType::FourOfAKind);
+        assert_eq!(hand_type(&rules, "23223"), HandType::FullHouse);
+        assert_eq!(hand_type(&rules, "22234"), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(&rules, "22334"), HandType::TwoPairs);
+        assert_eq!(hand_type(&rules, "32345"), HandType::OnePair);
+        assert_eq!(hand_type(&rules, "2J345"), HandType::HighCard);
+        assert!(HandType::FiveOfAKind > HandType::FourOfAKind);
+        assert!(HandType::OnePair > HandType::HighCard);
+    }
+
+    #[test]
+    fn test_joker_hand_types() {
+        let rules = Rules::jokers();
+        assert_eq!(hand_type(&rules, "J3456"), HandType::OnePair);
+        assert_eq!(hand_type(&rules, "22J22"), HandType::FiveOfAKind);
+        assert_eq!(hand_type(&rules, "223J2"), HandType::FourOfAKind);
+        assert_eq!(hand_type(&rules, "23J23"), HandType::FullHouse);
+        assert_eq!(hand_type(&rules, "22J34"), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(&rules, "2J334"), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(&rules, "JJJJJ"), HandType::FiveOfAKind);
+        let hand = parse_hand(&rules, "KTJJT 220").unwrap();
+        assert_eq!(
+            rules.substitute(&hand),
+            parse_hand(&rules, "KTTTT 220").unwrap().cards
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let rules = Rules::standard();
+        let weaker = parse_hand(&rules, "KTJJT 220").unwrap();
+        let stronger = parse_hand(&rules, "KK677 28").unwrap();
+        assert!(rules.sort_key(&weaker) < rules.sort_key(&stronger));
+        // With jokers both are four of a kind, and J loses to every other card.
+        let rules = Rules::jokers();
+        let weaker = parse_hand(&rules, "JKKK2 0").unwrap();
+        let stronger = parse_hand(&rules, "QQQQ2 0").unwrap();
+        assert!(rules.sort_key(&weaker) < rules.sort_key(&stronger));
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules = Rules::new("1234").wildcards("14").hand_size(3);
+        assert_eq!(hand_type(&rules, "143"), HandType::ThreeOfAKind);
+        assert_eq!(hand_type(&rules, "123"), HandType::OnePair);
+        assert_eq!(hand_type(&rules, "232"), HandType::OnePair);
+        let error = parse_hand(&rules, "1234 5").unwrap_err();
+        assert_eq!(error.span, Span::new(0, 4));
+        assert_eq!(error.expected, vec!["3 cards from 1234".to_string()]);
+        let error = parse_hand(&rules, "125 5").unwrap_err();
+        assert_eq!(error.span, Span::new(0, 3));
+        let error = parse_hand(&rules, "123").unwrap_err();
+        assert_eq!((error.column, error.found.as_str()), (4, "end of input"));
+        // Without a fixed size hands of any length are compared.
+        let rules = Rules::new("AB");
+        assert_eq!(hand_type(&rules, "AAAAAAB"), HandType::FiveOfAKind);
+        assert_eq!(hand_type(&rules, "AB"), HandType::HighCard);
+    }
+}
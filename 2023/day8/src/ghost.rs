@@ -0,0 +1,159 @@
+use common::number_theory::crt;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// Where a single ghost reaches a goal node. The walk is deterministic, so once a
+// (node, instruction index) state repeats it loops forever: the first `pre_period`
+// steps are walked once, then the next `length` steps repeat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Cycle {
+    pub(crate) pre_period: u64,
+    pub(crate) length: u64,
+    // Goal steps before the cycle starts, each seen only once.
+    pub(crate) early_hits: Vec<u64>,
+    // Goal steps inside the first turn of the cycle, repeating every `length` steps.
+    pub(crate) cycle_hits: Vec<u64>,
+}
+
+impl Cycle {
+    pub(crate) fn hits(&self, step: u64) -> bool {
+        if step < self.pre_period {
+            self.early_hits.contains(&step)
+        } else {
+            let in_cycle = self.pre_period + (step - self.pre_period) % self.length;
+            self.cycle_hits.contains(&in_cycle)
+        }
+    }
+}
+
+pub(crate) fn find_cycle<S, N, G>(start: S, mut next: N, is_goal: G) -> Cycle
+where
+    S: Hash + Eq + Clone,
+    N: FnMut(&S) -> S,
+    G: Fn(&S) -> bool,
+{
+    let mut seen = HashMap::new();
+    let mut hits = vec![];
+    let mut state = start;
+    let mut step = 0;
+    let pre_period = loop {
+        if let Some(first_seen) = seen.insert(state.clone(), step) {
+            break first_seen;
+        }
+        if is_goal(&state) {
+            hits.push(step);
+        }
+        state = next(&state);
+        step += 1;
+    };
+    let (early_hits, cycle_hits) = hits.into_iter().partition(|hit| *hit < pre_period);
+    Cycle {
+        pre_period,
+        length: step - pre_period,
+        early_hits,
+        cycle_hits,
+    }
+}
+
+// The first step at which every ghost stands on a goal node at the same time, or
+// `None` if that never happens.
+pub(crate) fn first_common_hit(cycles: &[Cycle]) -> Option<u64> {
+    let longest = cycles.iter().max_by_key(|cycle| cycle.pre_period)?;
+
+    // Before the last ghost settles into its cycle it can only be on a goal at one
+    // of its early hits.
+    let early = longest
+        .early_hits
+        .iter()
+        .find(|step| cycles.iter().all(|cycle| cycle.hits(**step)));
+    if early.is_some() {
+        return early.copied();
+    }
+
+    // After that every ghost repeats, so each choice of cycle hits is a system of
+    // congruences step = hit (mod length).
+    let mut classes = vec![(0, 1)];
+    for cycle in cycles {
+        let mut merged = cycle
+            .cycle_hits
+            .iter()
+            .flat_map(|hit| {
+                classes
+                    .iter()
+                    .filter_map(move |class| crt(*class, (*hit as u128, cycle.length as u128)))
+            })
+            .collect::<Vec<(u128, u128)>>();
+        merged.sort();
+        merged.dedup();
+        classes = merged;
+    }
+    let settled = longest.pre_period as u128;
+    classes
+        .into_iter()
+        .map(|(residue, modulus)| {
+            if residue >= settled {
+                residue
+            } else {
+                residue + (settled - residue).div_ceil(modulus) * modulus
+            }
+        })
+        .min()
+        .map(|step| step as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(pre_period: u64, length: u64, early_hits: Vec<u64>, cycle_hits: Vec<u64>) -> Cycle {
+        Cycle {
+            pre_period,
+            length,
+            early_hits,
+            cycle_hits,
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 ...
+        let found = find_cycle(0, |n| if *n == 4 { 2 } else { n + 1 }, |n| n % 2 == 1);
+        assert_eq!(found, cycle(2, 3, vec![1], vec![3]));
+        assert!(found.hits(1));
+        assert!(!found.hits(2));
+        assert!(found.hits(6));
+        assert!(found.hits(9));
+    }
+
+    #[test]
+    fn test_first_common_hit() {
+        // The classic puzzle shape: one hit per cycle, exactly at the cycle length.
+        let cycles = vec![cycle(1, 2, vec![], vec![2]), cycle(1, 3, vec![], vec![3])];
+        assert_eq!(first_common_hit(&cycles), Some(6));
+    }
+
+    #[test]
+    fn test_first_common_hit_with_offsets() {
+        let cycles = vec![
+            cycle(3, 4, vec![1], vec![4, 5]),
+            cycle(0, 6, vec![], vec![1, 5]),
+        ];
+        assert_eq!(first_common_hit(&cycles), Some(1));
+
+        let cycles = vec![
+            cycle(3, 4, vec![], vec![4, 5]),
+            cycle(0, 6, vec![], vec![1, 5]),
+        ];
+        assert_eq!(first_common_hit(&cycles), Some(5));
+
+        // Both congruences meet at 1, before the first ghost enters its cycle.
+        let cycles = vec![cycle(3, 4, vec![], vec![5]), cycle(0, 6, vec![], vec![1])];
+        assert_eq!(first_common_hit(&cycles), Some(13));
+    }
+
+    #[test]
+    fn test_first_common_hit_impossible() {
+        let cycles = vec![cycle(0, 4, vec![], vec![0]), cycle(0, 6, vec![], vec![1])];
+        assert_eq!(first_common_hit(&cycles), None);
+    }
+}
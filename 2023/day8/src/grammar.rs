@@ -0,0 +1,48 @@
+use common::parse::{cell, lines, newline, spaces, tag, word, Parser};
+
+// `LLR`: 0 for left and 1 for right, spaces allowed.
+fn instructions<'a>() -> impl Parser<'a, Vec<usize>> {
+    let instruction = cell("L or R", |c| match c {
+        'L' => Some(0),
+        'R' => Some(1),
+        _ => None,
+    });
+    spaces().ignore_then(instruction.then_ignore(spaces()).repeated1())
+}
+
+// `AAA = (BBB, CCC)`: a node and its left and right children, for IDs of any length.
+fn node<'a>() -> impl Parser<'a, (&'a str, [&'a str; 2])> {
+    word()
+        .padded()
+        .then_ignore(tag("="))
+        .then_ignore(tag("(").padded())
+        .then(word())
+        .then_ignore(tag(",").padded())
+        .then(word())
+        .then_ignore(tag(")").padded())
+        .map(|((id, left), right)| (id, [left, right]))
+}
+
+pub(crate) fn network<'a>() -> impl Parser<'a, (Vec<usize>, Vec<(&'a str, [&'a str; 2])>)> {
+    instructions()
+        .then_ignore(newline().repeated1())
+        .then(lines(node()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::parse::parse;
+
+    #[test]
+    fn test_network() {
+        let (instructions, nodes) = parse(network(), "LRL\n\nAAA = (BBB, CCC)\n").unwrap();
+        assert_eq!(instructions, vec![0, 1, 0]);
+        assert_eq!(nodes, vec![("AAA", ["BBB", "CCC"])]);
+        let error = parse(network(), "LRL\n\nAAA = (BBB CCC)").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 12));
+        assert_eq!(error.expected, vec!["\",\"".to_string()]);
+        let error = parse(network(), "LXR\n\nAAA = (BBB, CCC)").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 2));
+    }
+}
@@ -1,3 +1,6 @@
+mod ghost;
+mod grammar;
+mod network;
 mod part1;
 mod part2;
 
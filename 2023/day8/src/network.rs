@@ -0,0 +1,159 @@
+use crate::grammar;
+use common::parse::{parse, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug)]
+pub(crate) enum NetworkError {
+    // A node is named as a child but never gets a line of its own.
+    UndefinedNode(String),
+    Syntax(ParseError),
+}
+
+impl fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::UndefinedNode(id) => write!(f, "node {} is never defined", id),
+            NetworkError::Syntax(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+// The desert network with node IDs interned into dense indices, so walking it is
+// plain array lookups. `children[node]` holds the `[left, right]` indices.
+#[derive(Debug, Clone)]
+pub(crate) struct Network {
+    instructions: Vec<usize>,
+    names: Vec<String>,
+    children: Vec<[usize; 2]>,
+    indices: HashMap<String, usize>,
+}
+
+impl Network {
+    pub(crate) fn parse(input: &str) -> Result<Network, NetworkError> {
+        let (instructions, nodes) =
+            parse(grammar::network(), input).map_err(NetworkError::Syntax)?;
+        let mut network = Network {
+            instructions,
+            names: vec![],
+            children: vec![],
+            indices: HashMap::new(),
+        };
+        let mut defined = vec![];
+        for (id, [left, right]) in nodes {
+            let node = network.intern(id);
+            let left = network.intern(left);
+            let right = network.intern(right);
+            network.children[node] = [left, right];
+            defined.push(node);
+        }
+        let mut missing = vec![true; network.names.len()];
+        for node in defined {
+            missing[node] = false;
+        }
+        if let Some(node) = missing.iter().position(|&missing| missing) {
+            return Err(NetworkError::UndefinedNode(network.names[node].clone()));
+        }
+        Ok(network)
+    }
+
+    // Children can be mentioned before their own line, so they get an index on first
+    // sight and a self-loop placeholder until they are defined; `parse` rejects any
+    // placeholder that is left over.
+    fn intern(&mut self, id: &str) -> usize {
+        if let Some(index) = self.indices.get(id) {
+            return *index;
+        }
+        let index = self.names.len();
+        self.names.push(id.to_string());
+        self.children.push([index, index]);
+        self.indices.insert(id.to_string(), index);
+        index
+    }
+
+    pub(crate) fn instruction_count(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub(crate) fn node(&self, id: &str) -> Option<usize> {
+        self.indices.get(id).copied()
+    }
+
+    pub(crate) fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub(crate) fn nodes(&self) -> std::ops::Range<usize> {
+        0..self.names.len()
+    }
+
+    // Where `node` leads when the walk is at overall step `step`.
+    pub(crate) fn next(&self, node: usize, step: usize) -> usize {
+        self.children[node][self.instructions[step % self.instructions.len()]]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let network = Network::parse(
+            "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap();
+        assert_eq!(network.instruction_count(), 3);
+        let aaa = network.node("AAA").unwrap();
+        let bbb = network.node("BBB").unwrap();
+        let zzz = network.node("ZZZ").unwrap();
+        assert_eq!(network.next(aaa, 0), bbb);
+        assert_eq!(network.next(bbb, 1), aaa);
+        assert_eq!(network.next(bbb, 2), zzz);
+        assert_eq!(network.next(bbb, 5), zzz);
+        assert_eq!(network.name(zzz), "ZZZ");
+        assert_eq!(network.nodes().len(), 3);
+    }
+
+    #[test]
+    fn test_parse_any_id_length_and_spacing() {
+        let network = Network::parse(
+            "  L R
+
+start=(middle ,end)
+   middle   =   ( start,  end )
+end = (end, end)
+",
+        )
+        .unwrap();
+        assert_eq!(network.instruction_count(), 2);
+        let start = network.node("start").unwrap();
+        let middle = network.node("middle").unwrap();
+        let end = network.node("end").unwrap();
+        assert_eq!(network.next(start, 0), middle);
+        assert_eq!(network.next(start, 1), end);
+        assert_eq!(network.next(middle, 0), start);
+        assert_eq!(network.node("missing"), None);
+    }
+
+    #[test]
+    fn test_parse_undefined_node() {
+        let error = Network::parse(
+            "LR
+
+AAA = (BBB, ZZZ)
+ZZZ = (ZZZ, ZZZ)",
+        )
+        .unwrap_err();
+        assert!(matches!(&error, NetworkError::UndefinedNode(id) if id == "BBB"));
+        assert_eq!(error.to_string(), "node BBB is never defined");
+        assert!(matches!(
+            Network::parse("LR\n\nAAA = (BBB"),
+            Err(NetworkError::Syntax(_))
+        ));
+    }
+}
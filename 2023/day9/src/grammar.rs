@@ -0,0 +1,10 @@
+use common::parse::{integer, lines, Parser};
+
+// `0 3 6 9 12 15`, negative values included.
+pub(crate) fn history<'a>() -> impl Parser<'a, Vec<i128>> {
+    integer().padded().repeated1()
+}
+
+pub(crate) fn histories<'a>() -> impl Parser<'a, Vec<Vec<i128>>> {
+    lines(history())
+}
@@ -1,7 +1,47 @@
+mod grammar;
+mod oasis;
 mod part1;
 mod part2;
 
+use common::cli::usage_error;
+
 fn main() {
     part1::main();
     part2::main();
+
+    // `cargo run -- --extrapolate 10` sums the values 10 steps either side of every
+    // history, `--polynomials` prints the fitted coefficients
+    let args = std::env::args().collect::<Vec<String>>();
+    let content = std::fs::read_to_string("src/inputs/input.txt").unwrap();
+    if let Some(position) = args.iter().position(|arg| arg == "--extrapolate") {
+        let steps = args
+            .get(position + 1)
+            .and_then(|steps| steps.parse::<usize>().ok())
+            .unwrap_or_else(|| usage_error("cargo run -- --extrapolate <steps>"));
+        let sequences = oasis::parse_sequences(&content).unwrap();
+        let sum = |extrapolate: &dyn Fn(&oasis::Sequence) -> Result<i128, oasis::OasisError>| {
+            sequences.iter().try_fold(0i128, |sum, sequence| {
+                sum.checked_add(extrapolate(sequence)?)
+                    .ok_or(oasis::OasisError::Overflow)
+            })
+        };
+        println!("{} steps ahead: {:?}", steps, sum(&|s| s.forward(steps)));
+        println!("{} steps back: {:?}", steps, sum(&|s| s.backward(steps)));
+    }
+    if args.iter().any(|arg| arg == "--polynomials") {
+        for line in content.lines() {
+            let parsed =
+                oasis::Sequence::parse(line).and_then(|s| Ok((s.degree(), s.coefficients()?)));
+            match parsed {
+                Ok((degree, coefficients)) => {
+                    let terms = coefficients
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<String>>();
+                    println!("degree {}: {}", degree, terms.join(" "));
+                }
+                Err(error) => println!("{}", error),
+            }
+        }
+    }
 }
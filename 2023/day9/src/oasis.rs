@@ -0,0 +1,279 @@
+use crate::grammar;
+use common::number_theory::gcd;
+use common::parse::{parse, ParseError};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum OasisError {
+    // The differences ran out of values before a row of zeros appeared.
+    NeverSettles(Vec<i128>),
+    Overflow,
+    Syntax(ParseError),
+}
+
+impl fmt::Display for OasisError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OasisError::NeverSettles(values) => {
+                write!(f, "differences never reach all zeros for {:?}", values)
+            }
+            OasisError::Overflow => write!(f, "arithmetic overflow"),
+            OasisError::Syntax(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+// An exact fraction, always stored reduced with a positive denominator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct Rational {
+    numerator: i128,
+    denominator: i128,
+}
+
+impl Rational {
+    pub(crate) fn new(numerator: i128, denominator: i128) -> Option<Rational> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator.unsigned_abs(), denominator.unsigned_abs()) as i128;
+        let sign = denominator.signum();
+        Some(Rational {
+            numerator: numerator.checked_div(divisor)?.checked_mul(sign)?,
+            denominator: denominator.checked_div(divisor)?.checked_mul(sign)?,
+        })
+    }
+
+    pub(crate) fn integer(value: i128) -> Rational {
+        Rational {
+            numerator: value,
+            denominator: 1,
+        }
+    }
+
+    pub(crate) fn checked_add(self, other: Rational) -> Option<Rational> {
+        let numerator = self
+            .numerator
+            .checked_mul(other.denominator)?
+            .checked_add(other.numerator.checked_mul(self.denominator)?)?;
+        Rational::new(numerator, self.denominator.checked_mul(other.denominator)?)
+    }
+
+    pub(crate) fn checked_mul(self, other: Rational) -> Option<Rational> {
+        Rational::new(
+            self.numerator.checked_mul(other.numerator)?,
+            self.denominator.checked_mul(other.denominator)?,
+        )
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+// An OASIS history with its difference table. Row `j` holds the j-th differences, the
+// last row is the first one that is all zeros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Sequence {
+    rows: Vec<Vec<i128>>,
+}
+
+impl Sequence {
+    pub(crate) fn new(values: Vec<i128>) -> Result<Sequence, OasisError> {
+        let mut rows = vec![values];
+        loop {
+            let last = rows.last().unwrap();
+            if !last.is_empty() && last.iter().all(|value| *value == 0) {
+                return Ok(Sequence { rows });
+            }
+            if last.len() <= 1 {
+                return Err(OasisError::NeverSettles(rows.swap_remove(0)));
+            }
+            let next = last
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]).ok_or(OasisError::Overflow))
+                .collect::<Result<Vec<i128>, OasisError>>()?;
+            rows.push(next);
+        }
+    }
+
+    pub(crate) fn parse(line: &str) -> Result<Sequence, OasisError> {
+        parse(grammar::history(), line)
+            .map_err(OasisError::Syntax)
+            .and_then(Sequence::new)
+    }
+
+    pub(crate) fn values(&self) -> &[i128] {
+        &self.rows[0]
+    }
+
+    // The degree of the polynomial through the values; a constant has degree 0 and an
+    // all-zero history -1.
+    pub(crate) fn degree(&self) -> isize {
+        self.rows.len() as isize - 2
+    }
+
+    // The value at index `x` of the history (0 is the first known value), from
+    // Newton's forward formula: f(x) = sum of C(x, j) * first j-th difference.
+    pub(crate) fn value_at(&self, x: i128) -> Result<i128, OasisError> {
+        let mut binomial = 1i128;
+        let mut value = 0i128;
+        for (j, row) in self.rows.iter().enumerate() {
+            if j > 0 {
+                // C(x, j) = C(x, j - 1) * (x - j + 1) / j, and the division is exact.
+                let j = j as i128;
+                binomial = binomial
+                    .checked_mul(x - j + 1)
+                    .ok_or(OasisError::Overflow)?
+                    / j;
+            }
+            let term = binomial.checked_mul(row[0]).ok_or(OasisError::Overflow)?;
+            value = value.checked_add(term).ok_or(OasisError::Overflow)?;
+        }
+        Ok(value)
+    }
+
+    // The value `k` steps after the last known one.
+    pub(crate) fn forward(&self, k: usize) -> Result<i128, OasisError> {
+        self.value_at(self.values().len() as i128 - 1 + k as i128)
+    }
+
+    // The value `k` steps before the first known one.
+    pub(crate) fn backward(&self, k: usize) -> Result<i128, OasisError> {
+        self.value_at(-(k as i128))
+    }
+
+    // Coefficients a_0, a_1, ... of the polynomial with f(x) = a_0 + a_1 x + ...,
+    // x being the index in the history.
+    pub(crate) fn coefficients(&self) -> Result<Vec<Rational>, OasisError> {
+        let overflow = || OasisError::Overflow;
+        let mut coefficients = vec![Rational::integer(0); self.rows.len()];
+        // x (x - 1) ... (x - j + 1) as integer coefficients, and j!.
+        let mut falling = vec![1i128];
+        let mut factorial = 1i128;
+        for (j, row) in self.rows.iter().enumerate() {
+            if j > 0 {
+                let shift = j as i128 - 1;
+                let mut next = vec![0i128; falling.len() + 1];
+                for (power, coefficient) in falling.iter().enumerate() {
+                    next[power + 1] = next[power + 1]
+                        .checked_add(*coefficient)
+                        .ok_or_else(overflow)?;
+                    let lowered = coefficient.checked_mul(shift).ok_or_else(overflow)?;
+                    next[power] = next[power].checked_sub(lowered).ok_or_else(overflow)?;
+                }
+                falling = next;
+                factorial = factorial.checked_mul(j as i128).ok_or_else(overflow)?;
+            }
+            let scale = Rational::new(row[0], factorial).ok_or_else(overflow)?;
+            for (power, coefficient) in falling.iter().enumerate() {
+                let term = scale
+                    .checked_mul(Rational::integer(*coefficient))
+                    .ok_or_else(overflow)?;
+                coefficients[power] = coefficients[power].checked_add(term).ok_or_else(overflow)?;
+            }
+        }
+        while coefficients.len() > 1 && *coefficients.last().unwrap() == Rational::integer(0) {
+            coefficients.pop();
+        }
+        Ok(coefficients)
+    }
+}
+
+pub(crate) fn parse_sequences(content: &str) -> Result<Vec<Sequence>, OasisError> {
+    parse(grammar::histories(), content)
+        .map_err(OasisError::Syntax)?
+        .into_iter()
+        .map(Sequence::new)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(values: &[i128]) -> Sequence {
+        Sequence::new(values.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let history = sequence(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(history.degree(), 3);
+        assert_eq!(history.forward(1), Ok(68));
+        assert_eq!(history.backward(1), Ok(5));
+        assert_eq!(history.value_at(2), Ok(16));
+        let squares = sequence(&[1, 4, 9, 16]);
+        assert_eq!(squares.forward(3), Ok(49));
+        assert_eq!(squares.backward(3), Ok(4));
+        assert_eq!(sequence(&[7, 7, 7]).forward(100), Ok(7));
+        assert_eq!(sequence(&[0, 0]).backward(5), Ok(0));
+    }
+
+    #[test]
+    fn test_coefficients() {
+        let render = |values: &[i128]| {
+            sequence(values)
+                .coefficients()
+                .unwrap()
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+        };
+        // (x + 1)(x + 2) / 2
+        assert_eq!(render(&[1, 3, 6, 10, 15, 21]), vec!["1", "3/2", "1/2"]);
+        assert_eq!(render(&[0, 3, 6, 9, 12, 15]), vec!["0", "3"]);
+        assert_eq!(render(&[4, 4]), vec!["4"]);
+        assert_eq!(render(&[0, 0]), vec!["0"]);
+        // The polynomial reproduces the history on both sides of the known values.
+        let history = sequence(&[10, 13, 16, 21, 30, 45]);
+        let coefficients = history.coefficients().unwrap();
+        for x in -3..10 {
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(Rational::integer(0), |acc, c| {
+                    acc.checked_mul(Rational::integer(x))
+                        .unwrap()
+                        .checked_add(*c)
+                        .unwrap()
+                });
+            assert_eq!(value, Rational::integer(history.value_at(x).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            Sequence::new(vec![1, 2, 4]),
+            Err(OasisError::NeverSettles(vec![1, 2, 4]))
+        );
+        assert_eq!(Sequence::new(vec![]), Err(OasisError::NeverSettles(vec![])));
+        assert_eq!(
+            Sequence::new(vec![i128::MIN, i128::MAX]),
+            Err(OasisError::Overflow)
+        );
+        let half = i128::MAX / 2;
+        assert_eq!(
+            sequence(&[0, half, 2 * half]).forward(3),
+            Err(OasisError::Overflow)
+        );
+        match Sequence::parse("1 x") {
+            Err(OasisError::Syntax(error)) => {
+                assert_eq!((error.column, error.found.as_str()), (3, "\"x\""))
+            }
+            _ => panic!("\"1 x\" should not parse"),
+        }
+        assert_eq!(
+            parse_sequences("-1 -1\n-4 -4 -4\n").unwrap()[1].values(),
+            &[-4, -4, -4]
+        );
+        assert_eq!(Rational::new(2, -4), Rational::new(-1, 2));
+        assert_eq!(Rational::new(1, 0), None);
+    }
+}